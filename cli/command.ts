@@ -1,10 +1,24 @@
 import { program } from "commander";
 import { PublicKey } from "@solana/web3.js";
 import {
-  changeAdmin,
+  proposeAdmin,
+  acceptAdmin,
+  spendPoints,
+  setReferralConfig,
+  setFeeConfig,
+  setCreatorShare,
+  claimCreatorRoyalty,
+  startSeason,
+  endSeason,
+  settleUserSeason,
+  getLeaderboard,
   changeRewardMint,
+  startRewardMint,
+  finalizeRewardMint,
   changeRewardEnable,
   changeRewardPerDay,
+  executeConfigChange,
+  cancelConfigChange,
   claimReward,
   getGlobalInfo,
   initProject,
@@ -31,9 +45,11 @@ programCommand("status")
   });
 
 programCommand("init")
+  .option("-c, --max_reward_per_day <number>", "cap of reward per day")
+  .option("-p, --points_mode", "accrue off-chain points instead of reward token", false)
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
   .action(async (directory, cmd) => {
-    const { env, keypair, rpc } = cmd.opts();
+    const { env, keypair, rpc, max_reward_per_day, points_mode } = cmd.opts();
 
     console.log("Solana Cluster:", env);
     console.log("Keypair Path:", keypair);
@@ -41,10 +57,15 @@ programCommand("init")
 
     await setClusterConfig(env, keypair, rpc);
 
-    await initProject();
+    if (max_reward_per_day === undefined) {
+      console.log("Error Reward Cap Input");
+      return;
+    }
+
+    await initProject(max_reward_per_day, points_mode);
   });
 
-programCommand("change-admin")
+programCommand("propose-admin")
   .option("-a, --new_admin <string>", "new admin address")
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
   .action(async (directory, cmd) => {
@@ -61,7 +82,186 @@ programCommand("change-admin")
     }
 
     //  update global info
-    await changeAdmin(new_admin);
+    await proposeAdmin(new_admin);
+  });
+
+programCommand("accept-admin")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    //  update global info
+    await acceptAdmin();
+  });
+
+programCommand("spend-points")
+  .option("-a, --user_address <string>", "user address")
+  .option("-n, --amount <number>", "points to spend")
+  .option("-i, --redemption_id <number>", "off-chain redemption id", "0")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, user_address, amount, redemption_id } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    if (user_address === undefined || amount === undefined) {
+      console.log("Error Spend Points Input");
+      return;
+    }
+
+    await spendPoints(new PublicKey(user_address), amount, redemption_id);
+  });
+
+programCommand("set-fee")
+  .option("-t, --treasury <string>", "treasury address")
+  .option("-b, --claim_fee_bps <number>", "claim fee in bps of claimed reward", "0")
+  .option("-l, --lock_fee <number>", "lock / unlock fee in lamports", "0")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, treasury, claim_fee_bps, lock_fee } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    if (treasury === undefined) {
+      console.log("Error Fee Input");
+      return;
+    }
+
+    await setFeeConfig(new PublicKey(treasury), Number(claim_fee_bps), Number(lock_fee));
+  });
+
+programCommand("set-creator-share")
+  .option("-b, --creator_share_bps <number>", "share of staking reward paid to NFT creators in bps")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, creator_share_bps } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    if (creator_share_bps === undefined) {
+      console.log("Error Creator Share Input");
+      return;
+    }
+
+    await setCreatorShare(Number(creator_share_bps));
+  });
+
+programCommand("claim-royalty")
+  .option("-m, --mint <string>", "staked nft mint")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, mint } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    if (mint === undefined) {
+      console.log("Error Claim Royalty Input");
+      return;
+    }
+
+    await claimCreatorRoyalty(new PublicKey(mint));
+  });
+
+programCommand("set-referral")
+  .option("-b, --referral_bps <number>", "referral bonus in bps of referred users' claims")
+  .option("-c, --referral_cap <number>", "total bonus cap per referrer")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, referral_bps, referral_cap } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    if (referral_bps === undefined || referral_cap === undefined) {
+      console.log("Error Referral Input");
+      return;
+    }
+
+    await setReferralConfig(Number(referral_bps), Number(referral_cap));
+  });
+
+programCommand("start-season")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    await startSeason();
+  });
+
+programCommand("end-season")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    await endSeason();
+  });
+
+programCommand("settle-season")
+  .option("-a, --user_address <string>", "user address")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, user_address } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    if (user_address === undefined) {
+      console.log("Error Settle Season Input");
+      return;
+    }
+
+    await settleUserSeason(new PublicKey(user_address));
+  });
+
+programCommand("leaderboard")
+  .option("-s, --season <number>", "season id")
+  .option("-n, --limit <number>", "number of entries", "20")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc, season, limit } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    if (season === undefined) {
+      console.log("Error Leaderboard Input");
+      return;
+    }
+
+    await getLeaderboard(Number(season), Number(limit));
   });
 
 programCommand("change-reward-per-day")
@@ -106,9 +306,12 @@ programCommand("change-reward-enable")
 
 programCommand("change-reward-mint")
   .option("-m, --new_mint <string>", "new reward mint")
+  .option("-w, --claim_window <number>", "legacy reward claim window in seconds", "604800")
+  .option("-n, --ratio_num <number>", "new mint amount per ratio_den legacy amount", "1")
+  .option("-d, --ratio_den <number>", "legacy mint amount per ratio_num new amount", "1")
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
   .action(async (directory, cmd) => {
-    const { env, keypair, rpc, new_mint } = cmd.opts();
+    const { env, keypair, rpc, new_mint, claim_window, ratio_num, ratio_den } = cmd.opts();
 
     console.log("Solana Cluster:", env);
     console.log("Keypair Path:", keypair);
@@ -121,14 +324,70 @@ programCommand("change-reward-mint")
     }
 
     //  update global info
-    await changeRewardMint(new_mint);
+    await changeRewardMint(new_mint, claim_window, ratio_num, ratio_den);
+  });
+
+programCommand("start-reward-mint")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    //  update global info
+    await startRewardMint();
+  });
+
+programCommand("finalize-reward-mint")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    //  update global info
+    await finalizeRewardMint();
+  });
+
+programCommand("execute-config-change")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    //  update global info
+    await executeConfigChange();
+  });
+
+programCommand("cancel-config-change")
+  // eslint-disable-next-line @typescript-eslint/no-unused-vars
+  .action(async (directory, cmd) => {
+    const { env, keypair, rpc } = cmd.opts();
+
+    console.log("Solana Cluster:", env);
+    console.log("Keypair Path:", keypair);
+    console.log("RPC URL:", rpc);
+    await setClusterConfig(env, keypair, rpc);
+
+    await cancelConfigChange();
   });
 
 programCommand("lock")
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
   .option("-m, --mint <string>")
+  .option("-t, --receipt", "mint non-transferable staking receipt", false)
   .action(async (directory, cmd) => {
-    const { env, keypair, rpc, mint } = cmd.opts();
+    const { env, keypair, rpc, mint, receipt } = cmd.opts();
 
     console.log("Solana Cluster:", env);
     console.log("Keypair Path:", keypair);
@@ -140,7 +399,7 @@ programCommand("lock")
       return;
     }
 
-    await lockPnft(new PublicKey(mint));
+    await lockPnft(new PublicKey(mint), receipt);
   });
 
 programCommand("unlock")
@@ -192,9 +451,10 @@ programCommand("user-status")
   });
 
   programCommand("init-user")
+  .option("-f, --referrer <string>", "referrer address")
   // eslint-disable-next-line @typescript-eslint/no-unused-vars
   .action(async (directory, cmd) => {
-    const { env, keypair, rpc } = cmd.opts();
+    const { env, keypair, rpc, referrer } = cmd.opts();
 
     console.log("Solana Cluster:", env);
     console.log("Keypair Path:", keypair);
@@ -202,7 +462,7 @@ programCommand("user-status")
 
     await setClusterConfig(env, keypair, rpc);
 
-    await initializeUserPool();
+    await initializeUserPool(referrer === undefined ? null : new PublicKey(referrer));
   });
 
 function programCommand(name: string) {
@@ -229,7 +489,7 @@ program.parse(process.argv);
 /*
 
 yarn script init
-yarn script change-admin -n J9ja5QkewwMi9kG6JkCNxfLK9CoDGk3F4hZTNKQaKZe3
+yarn script propose-admin -a J9ja5QkewwMi9kG6JkCNxfLK9CoDGk3F4hZTNKQaKZe3
 yarn script lock -m BV3bvkBqVawTghH4uCaba3MGgYs63XyxwX9CeULwvmKG
 
 https://solana-mainnet.g.alchemy.com/v2/wsOJ8IVuGPfyljRfcZjpLrsVQu0_of-j
@@ -13,10 +13,24 @@ import {
 
 import { IDL } from "../target/types/staking";
 import {
-  changeAdminTx,
-  changeRewardMintTx,
+  proposeAdminTx,
+  acceptAdminTx,
+  spendPointsTx,
+  setReferralConfigTx,
+  setFeeConfigTx,
+  setCreatorShareTx,
+  claimCreatorRoyaltyTx,
+  startSeasonTx,
+  endSeasonTx,
+  settleUserSeasonTx,
+  getSeasonLeaderboard,
+  queueRewardMintMigrationTx,
+  startRewardMintMigrationTx,
+  finalizeRewardMintMigrationTx,
   changeRewardEnableTx,
   changeRewardPerDayTx,
+  executeConfigChangeTx,
+  cancelConfigChangeTx,
   claimRewardTx,
   createInitUserTx,
   createInitializeTx,
@@ -25,6 +39,7 @@ import {
   getGlobalState,
   getUserState,
   getRewardVault,
+  getPendingReward,
 } from "../lib/scripts";
 
 let solConnection: Connection = null;
@@ -79,7 +94,7 @@ export const setClusterConfig = async (
 /**
  * Initialize global pool, vault
  */
-export const initProject = async () => {
+export const initProject = async (maxRewardPerDay: number, pointsMode: boolean) => {
   try {
     const updateCpIx = ComputeBudgetProgram.setComputeUnitPrice({
       microLamports: 5_000_000,
@@ -91,7 +106,7 @@ export const initProject = async () => {
     const tx = new Transaction().add(
       updateCpIx,
       updateCuIx,
-      await createInitializeTx(payer.publicKey, program)
+      await createInitializeTx(payer.publicKey, maxRewardPerDay, pointsMode, program)
     );
     const { blockhash, lastValidBlockHeight } =
       await solConnection.getLatestBlockhash();
@@ -111,9 +126,9 @@ export const initProject = async () => {
 };
 
 /**
- * Change admin of the program
+ * Propose new admin of the program
  */
-export const changeAdmin = async (newAdmin: string) => {
+export const proposeAdmin = async (newAdmin: string) => {
   let newAdminAddr = null;
   try {
     newAdminAddr = new PublicKey(newAdmin);
@@ -124,7 +139,20 @@ export const changeAdmin = async (newAdmin: string) => {
     ).publicKey;
   }
 
-  const tx = await changeAdminTx(payer.publicKey, newAdminAddr, program);
+  const tx = await proposeAdminTx(payer.publicKey, newAdminAddr, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Accept admin transfer as pending admin
+ */
+export const acceptAdmin = async () => {
+  const tx = await acceptAdminTx(payer.publicKey, program);
 
   const txId = await provider.sendAndConfirm(tx, [], {
     commitment: "confirmed",
@@ -161,7 +189,12 @@ export const changeRewardEnable = async (newRewardEnable: number) => {
   console.log("txHash: ", txId);
 };
 
-export const changeRewardMint = async (newMint: string) => {
+export const changeRewardMint = async (
+  newMint: string,
+  claimWindow: number,
+  ratioNum: number,
+  ratioDen: number
+) => {
   let newMintAddr = null;
   try {
     newMintAddr = new PublicKey(newMint);
@@ -171,7 +204,178 @@ export const changeRewardMint = async (newMint: string) => {
       { skipValidation: true }
     ).publicKey;
   }
-  const tx = await changeRewardMintTx(payer.publicKey, newMintAddr, program);
+  const tx = await queueRewardMintMigrationTx(
+    payer.publicKey,
+    newMintAddr,
+    claimWindow,
+    ratioNum,
+    ratioDen,
+    program
+  );
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Start queued reward mint migration after its eta
+ */
+export const startRewardMint = async () => {
+  const tx = await startRewardMintMigrationTx(payer.publicKey, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Switch to new reward mint after legacy claim window
+ */
+export const finalizeRewardMint = async () => {
+  const tx = await finalizeRewardMintMigrationTx(payer.publicKey, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Apply queued config change after its eta
+ */
+export const executeConfigChange = async () => {
+  const tx = await executeConfigChangeTx(payer.publicKey, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Cancel queued config change
+ */
+export const cancelConfigChange = async () => {
+  const tx = await cancelConfigChangeTx(payer.publicKey, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Spend user's points after an off-chain redemption
+ */
+export const spendPoints = async (
+  user: PublicKey,
+  amount: number,
+  redemptionId: number
+) => {
+  const tx = await spendPointsTx(payer.publicKey, user, amount, redemptionId, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Queue the share of staking reward paid to NFT creators
+ */
+export const setCreatorShare = async (creatorShareBps: number) => {
+  const tx = await setCreatorShareTx(payer.publicKey, creatorShareBps, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Claim creator royalty accrued on a staked NFT
+ */
+export const claimCreatorRoyalty = async (mint: PublicKey) => {
+  const tx = await claimCreatorRoyaltyTx(payer.publicKey, mint, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Queue treasury, claim fee bps and lock fee lamports change
+ */
+export const setFeeConfig = async (treasury: PublicKey, claimFeeBps: number, lockFee: number) => {
+  const tx = await setFeeConfigTx(payer.publicKey, treasury, claimFeeBps, lockFee, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Set referral bonus bps and the total bonus cap per referrer
+ */
+export const setReferralConfig = async (referralBps: number, referralCap: number) => {
+  const tx = await setReferralConfigTx(payer.publicKey, referralBps, referralCap, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Start the next season
+ */
+export const startSeason = async () => {
+  const tx = await startSeasonTx(payer.publicKey, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * End the active season
+ */
+export const endSeason = async () => {
+  const tx = await endSeasonTx(payer.publicKey, program);
+
+  const txId = await provider.sendAndConfirm(tx, [], {
+    commitment: "confirmed",
+  });
+
+  console.log("txHash: ", txId);
+};
+
+/**
+ * Settle user's accrual in the seasons ended since the last checkpoint
+ */
+export const settleUserSeason = async (userAddress: PublicKey) => {
+  const tx = await settleUserSeasonTx(payer.publicKey, userAddress, program);
+  if (tx.instructions.length === 0) {
+    console.log("User pool is up to date with the current season");
+    return;
+  }
 
   const txId = await provider.sendAndConfirm(tx, [], {
     commitment: "confirmed",
@@ -180,12 +384,22 @@ export const changeRewardMint = async (newMint: string) => {
   console.log("txHash: ", txId);
 };
 
+/**
+ * Print leaderboard of a season
+ */
+export const getLeaderboard = async (season: number, limit: number) => {
+  const leaderboard = await getSeasonLeaderboard(season, program);
+  leaderboard.slice(0, limit).forEach((entry, index) => {
+    console.log(`${index + 1}. ${entry.owner.toBase58()} ${entry.reward.toString()}`);
+  });
+};
+
 /**
  * Initialize user pool
  */
-export const initializeUserPool = async () => {
+export const initializeUserPool = async (referrer: PublicKey | null) => {
   try {
-    const tx = await createInitUserTx(payer.publicKey, program);
+    const tx = await createInitUserTx(payer.publicKey, program, referrer);
 
     const txId = await provider.sendAndConfirm(tx, [], {
       commitment: "confirmed",
@@ -197,13 +411,14 @@ export const initializeUserPool = async () => {
   }
 };
 
-export const lockPnft = async (nftMint: PublicKey) => {
+export const lockPnft = async (nftMint: PublicKey, withReceipt: boolean) => {
   try {
     const tx = await createLockPnftTx(
       payer as Wallet,
       nftMint,
       program,
-      solConnection
+      solConnection,
+      withReceipt
     );
 
     await addAdminSignAndConfirm(tx);
@@ -246,6 +461,11 @@ export const getUserInfo = async (
   const { data, key } = await getUserState(user, program);
   console.log("userPoolKey: ", key.toBase58());
   console.log(data);
+  const pendingReward = await getPendingReward(user, program);
+  console.log("Claimable reward: ", pendingReward.total.toString());
+  console.log("Creator royalty: ", pendingReward.creatorRoyalty.toString());
+  console.log("Claim fee: ", pendingReward.claimFee.toString());
+  console.log("Net reward: ", pendingReward.net.toString());
 };
 
 export const getGlobalInfo = async () => {
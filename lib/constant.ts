@@ -2,6 +2,20 @@ import { PublicKey } from "@solana/web3.js";
 
 export const GLOBAL_AUTHORITY_SEED = "global-authority";
 export const USER_POOL_SEED = "user-pool";
+export const CONFIG_CHANGE_SEED = "config-change";
+export const VOTER_WEIGHT_SEED = "voter-weight-record";
+export const RECEIPT_SEED = "receipt";
+export const SEASON_SEED = "season";
+export const USER_SEASON_SEED = "user-season";
+export const STAKE_CREATORS_SEED = "stake-creators";
+export const HOOK_AUTHORITY_SEED = "hook-authority";
+export const MIGRATION_ARCHIVE_SEED = "migration-archive";
+export const NFT_VOTE_RECORD_SEED = "nft-vote-record";
+
+export const PAUSE_LOCK = 1 << 0;
+export const PAUSE_CLAIM = 1 << 1;
+// Only accepted while emergency mode is on, emergency_unlock is never paused
+export const PAUSE_UNLOCK = 1 << 2;
 
 export const PROGRAM_ID = new PublicKey(
   "BXxRvgXLLh3Vfah9Ed9AnPGXo64GGqyit6jDku5sPrds"
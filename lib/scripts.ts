@@ -1,5 +1,6 @@
 import * as anchor from "@coral-xyz/anchor";
 import {
+  AccountMeta,
   PublicKey,
   Connection,
   SystemProgram,
@@ -9,7 +10,12 @@ import {
 } from "@solana/web3.js";
 import { Wallet } from "@coral-xyz/anchor";
 
-import { TOKEN_PROGRAM_ID, ASSOCIATED_TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
 import { PROGRAM_ID as TOKEN_AUTH_RULES_ID } from "@metaplex-foundation/mpl-token-auth-rules";
 
 import {
@@ -22,15 +28,27 @@ import {
   getMetadata,
 } from "./util";
 import {
-  ADMIN_ADDRESS,
+  CONFIG_CHANGE_SEED,
   GLOBAL_AUTHORITY_SEED,
+  HOOK_AUTHORITY_SEED,
+  MIGRATION_ARCHIVE_SEED,
+  NFT_VOTE_RECORD_SEED,
   REWARD_TOKEN_MINT,
+  RECEIPT_SEED,
+  SEASON_SEED,
+  STAKE_CREATORS_SEED,
   USER_POOL_SEED,
+  USER_SEASON_SEED,
+  VOTER_WEIGHT_SEED,
 } from "./constant";
+import { PendingReward, Role, UserSeason, VoterWeightAction } from "./types";
 
 export const createInitializeTx = async (
   admin: PublicKey,
-  program: anchor.Program
+  maxRewardPerDay: number,
+  pointsMode: boolean,
+  program: anchor.Program,
+  rewardMint: PublicKey = REWARD_TOKEN_MINT
 ) => {
   const [globalPool] = PublicKey.findProgramAddressSync(
     [Buffer.from(GLOBAL_AUTHORITY_SEED)],
@@ -39,10 +57,11 @@ export const createInitializeTx = async (
   console.log("globalPool: ", globalPool.toBase58());
 
   const tx = await program.methods
-    .initialize()
+    .initialize(new anchor.BN(maxRewardPerDay), pointsMode)
     .accounts({
       admin,
       globalPool,
+      rewardMint,
       systemProgram: SystemProgram.programId,
       rent: SYSVAR_RENT_PUBKEY,
     })
@@ -52,23 +71,29 @@ export const createInitializeTx = async (
 };
 
 /**
- * Change admin of the program as old admin
+ * Apply queued config change once its eta has passed
  */
-export const changeAdminTx = async (
-  admin: PublicKey,
-  newAdminAddr: PublicKey,
+export const executeConfigChangeTx = async (
+  payer: PublicKey,
   program: anchor.Program
 ) => {
   const [globalPool] = PublicKey.findProgramAddressSync(
     [Buffer.from(GLOBAL_AUTHORITY_SEED)],
     program.programId
   );
+  const [configChange] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CONFIG_CHANGE_SEED)],
+    program.programId
+  );
+  const configChangeData = await program.account.configChange.fetch(configChange);
 
   const tx = await program.methods
-    .changeRewardEnv(newAdminAddr, null, null, null)
+    .executeConfigChange()
     .accounts({
-      admin,
+      payer,
       globalPool,
+      configChange,
+      proposer: configChangeData.proposer,
     })
     .transaction();
 
@@ -76,23 +101,29 @@ export const changeAdminTx = async (
 };
 
 /**
- * Change reward rate per day as admin
+ * Cancel queued config change as operator or admin
  */
-export const changeRewardPerDayTx = async (
-  admin: PublicKey,
-  newReward: number,
+export const cancelConfigChangeTx = async (
+  authority: PublicKey,
   program: anchor.Program
 ) => {
   const [globalPool] = PublicKey.findProgramAddressSync(
     [Buffer.from(GLOBAL_AUTHORITY_SEED)],
     program.programId
   );
+  const [configChange] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CONFIG_CHANGE_SEED)],
+    program.programId
+  );
+  const configChangeData = await program.account.configChange.fetch(configChange);
 
   const tx = await program.methods
-    .changeRewardEnv(null, null, null, new anchor.BN(newReward))
+    .cancelConfigChange()
     .accounts({
-      admin,
+      authority,
       globalPool,
+      configChange,
+      proposer: configChangeData.proposer,
     })
     .transaction();
 
@@ -100,11 +131,11 @@ export const changeRewardPerDayTx = async (
 };
 
 /**
- * Change reward mint as admin
+ * Propose new admin of the program as current admin
  */
-export const changeRewardMintTx = async (
+export const proposeAdminTx = async (
   admin: PublicKey,
-  newRewardMint: PublicKey,
+  newAdminAddr: PublicKey,
   program: anchor.Program
 ) => {
   const [globalPool] = PublicKey.findProgramAddressSync(
@@ -113,7 +144,7 @@ export const changeRewardMintTx = async (
   );
 
   const tx = await program.methods
-    .changeRewardEnv(null, newRewardMint, null, null)
+    .proposeAdmin(newAdminAddr)
     .accounts({
       admin,
       globalPool,
@@ -124,21 +155,21 @@ export const changeRewardMintTx = async (
 };
 
 /**
- * Enable / disable reward as admin
+ * Accept admin transfer as pending admin
  */
-export const changeRewardEnableTx = async (
-  admin: PublicKey,
-  newState: boolean,
+export const acceptAdminTx = async (
+  pendingAdmin: PublicKey,
   program: anchor.Program
 ) => {
   const [globalPool] = PublicKey.findProgramAddressSync(
     [Buffer.from(GLOBAL_AUTHORITY_SEED)],
     program.programId
   );
+
   const tx = await program.methods
-    .changeRewardEnv(null, null, newState, null)
+    .acceptAdmin()
     .accounts({
-      admin,
+      pendingAdmin,
       globalPool,
     })
     .transaction();
@@ -147,102 +178,182 @@ export const changeRewardEnableTx = async (
 };
 
 /**
- * Initialize UserPool PDA
+ * Cancel pending admin transfer as current admin
  */
-export const createInitUserTx = async (
-  userAddress: PublicKey,
+export const cancelAdminTransferTx = async (
+  admin: PublicKey,
   program: anchor.Program
 ) => {
-  const [userPool] = PublicKey.findProgramAddressSync([
-    Buffer.from(USER_POOL_SEED),
-    userAddress.toBytes(),
-  ], program.programId);
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
 
   const tx = await program.methods
-    .initUser()
+    .cancelAdminTransfer()
     .accounts({
-      user: userAddress,
-      userPool,
-      systemProgram: SystemProgram.programId,
-      rent: SYSVAR_RENT_PUBKEY,
+      admin,
+      globalPool,
     })
     .transaction();
 
   return tx;
 };
 
-export const createLockPnftTx = async (
-  wallet: Wallet,
-  nftMint: PublicKey,
-  program: anchor.Program,
-  connection: Connection
+/**
+ * Queue reward rate per day change as operator
+ */
+export const changeRewardPerDayTx = async (
+  operator: PublicKey,
+  newReward: number,
+  program: anchor.Program
 ) => {
-  const userAddress = wallet.publicKey;
-
   const [globalPool] = PublicKey.findProgramAddressSync(
     [Buffer.from(GLOBAL_AUTHORITY_SEED)],
     program.programId
   );
 
-  const [userPool] = PublicKey.findProgramAddressSync([
-    Buffer.from(USER_POOL_SEED),
-    userAddress.toBytes(),
-  ], program.programId);
+  const [configChange] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CONFIG_CHANGE_SEED)],
+    program.programId
+  );
 
-  const nftEdition = await getMasterEdition(nftMint);
+  const tx = await program.methods
+    .queueConfigChange(null, new anchor.BN(newReward), null, null, null)
+    .accounts({
+      operator,
+      globalPool,
+      configChange,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
 
-  let tokenAccount = await getAssociatedTokenAccount(userAddress, nftMint);
+  return tx;
+};
 
-  const mintMetadata = await getMetadata(nftMint);
+/**
+ * Queue switching reward mint as operator, it can be started after the config delay
+ * Rewards accrued until the start can be claimed in current mint for claimWindow seconds,
+ * then they are converted to new mint with ratioNum / ratioDen
+ */
+export const queueRewardMintMigrationTx = async (
+  operator: PublicKey,
+  newRewardMint: PublicKey,
+  claimWindow: number,
+  ratioNum: number,
+  ratioDen: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+  const [configChange] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CONFIG_CHANGE_SEED)],
+    program.programId
+  );
 
-  const tokenMintRecord = findTokenRecordPda(nftMint, tokenAccount);
+  const tx = await program.methods
+    .queueRewardMintMigration(
+      new anchor.BN(claimWindow),
+      new anchor.BN(ratioNum),
+      new anchor.BN(ratioDen)
+    )
+    .accounts({
+      operator,
+      globalPool,
+      configChange,
+      newRewardMint,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
 
-  const tx = new Transaction();
+  return tx;
+};
 
-  let poolAccount = await connection.getAccountInfo(userPool);
-  if (poolAccount === null || poolAccount.data === null) {
-    const tx_initUserPool = await createInitUserTx(
-      userAddress,
-      program
-    );
-    tx.add(tx_initUserPool);
+/**
+ * MigrationArchive PDA of given reward epoch
+ */
+export const getMigrationArchivePda = (epoch: number, program: anchor.Program) => {
+  const epochId = Buffer.alloc(2);
+  epochId.writeUInt16LE(epoch);
+  const [migrationArchive] = PublicKey.findProgramAddressSync(
+    [Buffer.from(MIGRATION_ARCHIVE_SEED), epochId],
+    program.programId
+  );
+  return migrationArchive;
+};
+
+/**
+ * Start the queued reward mint migration once its eta has passed
+ */
+export const startRewardMintMigrationTx = async (
+  payer: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+  const [configChange] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CONFIG_CHANGE_SEED)],
+    program.programId
+  );
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  const configChangeData = await program.account.configChange.fetch(configChange);
+  const newMigration = configChangeData.newMigration as any;
+  if (!newMigration) {
+    throw new Error("No reward mint migration is queued");
   }
-  const txId = await program.methods
-    .lockPnft()
+
+  const tx = await program.methods
+    .startRewardMintMigration()
     .accounts({
-      user: userAddress,
+      payer,
       globalPool,
-      userPool: userPool,
-      tokenMint: nftMint,
-      tokenAccount,
-      tokenMintEdition: nftEdition,
-      tokenMintRecord,
-      mintMetadata,
-      authRules: MPL_DEFAULT_RULE_SET,
-      sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
-      tokenProgram: TOKEN_PROGRAM_ID,
-      tokenMetadataProgram: METAPLEX,
-      authRulesProgram: TOKEN_AUTH_RULES_ID,
+      configChange,
+      proposer: configChangeData.proposer,
+      migrationArchive: getMigrationArchivePda(globalPoolData.rewardEpoch as number, program),
+      newRewardMint: newMigration.newMint,
       systemProgram: SystemProgram.programId,
     })
     .transaction();
 
-  tx.add(txId);
+  return tx;
+};
 
-  tx.feePayer = userAddress;
-  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+/**
+ * Switch to new reward mint once legacy claim window is over
+ */
+export const finalizeRewardMintMigrationTx = async (
+  payer: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
 
-  const txData = await wallet.signTransaction(tx);
+  const tx = await program.methods
+    .finalizeRewardMintMigration()
+    .accounts({
+      payer,
+      globalPool,
+    })
+    .transaction();
 
-  return txData.serialize({ requireAllSignatures: false });
+  return tx;
 };
 
-export const claimRewardTx = async (
+/**
+ * Settle user's rewards accrued before the last reward mint migration
+ */
+export const settleRewardEpochTx = async (
+  payer: PublicKey,
   userAddress: PublicKey,
-  program: anchor.Program,
-  connection: Connection
+  program: anchor.Program
 ) => {
-  const [globalPool] = await PublicKey.findProgramAddress(
+  const [globalPool] = PublicKey.findProgramAddressSync(
     [Buffer.from(GLOBAL_AUTHORITY_SEED)],
     program.programId
   );
@@ -252,94 +363,1502 @@ export const claimRewardTx = async (
     userAddress.toBytes(),
   ], program.programId);
 
-  let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
-    connection,
-    userAddress,
-    userAddress,
-    [REWARD_TOKEN_MINT]
-  );
-
-  const rewardVault = await getAssociatedTokenAccount(
-    globalPool,
-    REWARD_TOKEN_MINT
-  );
+  // Archives of every reward epoch the user skipped
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  const userPoolData = await program.account.userPool.fetch(userPool);
+  const archives: AccountMeta[] = [];
+  for (
+    let epoch = userPoolData.rewardEpoch as number;
+    epoch < (globalPoolData.rewardEpoch as number);
+    epoch++
+  ) {
+    archives.push({
+      pubkey: getMigrationArchivePda(epoch, program),
+      isSigner: false,
+      isWritable: false,
+    });
+  }
 
-  const txId = await program.methods
-    .claimReward()
+  const tx = await program.methods
+    .settleRewardEpoch()
     .accounts({
-      user: userAddress,
+      payer,
       globalPool,
-      userPool: userPool,
-      rewardMint: REWARD_TOKEN_MINT,
-      rewardVault,
-      userRewardAccount: destinationAccounts[0],
-      tokenProgram: TOKEN_PROGRAM_ID,
-      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-      SystemProgram: SystemProgram.programId
+      userPool,
     })
-    .preInstructions([...instructions])
+    .remainingAccounts(archives)
     .transaction();
 
-  return txId;
+  return tx;
 };
 
-export const createUnlockPnftTx = async (
-  wallet: Wallet,
-  nftMint: PublicKey,
-  program: anchor.Program,
-  connection: Connection
+/**
+ * Settle creators' royalty of an NFT accrued before the last reward mint migration
+ */
+export const settleRoyaltyEpochTx = async (
+  payer: PublicKey,
+  mint: PublicKey,
+  program: anchor.Program
 ) => {
-  const userAddress = wallet.publicKey;
-
   const [globalPool] = PublicKey.findProgramAddressSync(
     [Buffer.from(GLOBAL_AUTHORITY_SEED)],
     program.programId
   );
 
-  const [userPool] = PublicKey.findProgramAddressSync([
-    Buffer.from(USER_POOL_SEED),
-    userAddress.toBytes(),
-  ], program.programId);
-
-  const nftEdition = await getMasterEdition(nftMint);
+  const [stakeCreators] = PublicKey.findProgramAddressSync(
+    [Buffer.from(STAKE_CREATORS_SEED), mint.toBytes()],
+    program.programId
+  );
 
-  let tokenAccount = await getAssociatedTokenAccount(userAddress, nftMint);
+  // Archives of every reward epoch the stake creators skipped
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  const stakeCreatorsData = await program.account.stakeCreators.fetch(stakeCreators);
+  const archives: AccountMeta[] = [];
+  for (
+    let epoch = stakeCreatorsData.rewardEpoch as number;
+    epoch < (globalPoolData.rewardEpoch as number);
+    epoch++
+  ) {
+    archives.push({
+      pubkey: getMigrationArchivePda(epoch, program),
+      isSigner: false,
+      isWritable: false,
+    });
+  }
 
-  const mintMetadata = await getMetadata(nftMint);
+  const tx = await program.methods
+    .settleRoyaltyEpoch()
+    .accounts({
+      payer,
+      globalPool,
+      stakeCreators,
+    })
+    .remainingAccounts(archives)
+    .transaction();
 
-  const tokenMintRecord = findTokenRecordPda(nftMint, tokenAccount);
+  return tx;
+};
 
-  const tx = new Transaction();
+/**
+ * Queue enable / disable reward as operator
+ */
+export const changeRewardEnableTx = async (
+  operator: PublicKey,
+  newState: boolean,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+  const [configChange] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CONFIG_CHANGE_SEED)],
+    program.programId
+  );
 
-  const txId = await program.methods
-    .unlockPnft()
+  const tx = await program.methods
+    .queueConfigChange(newState, null, null, null, null)
     .accounts({
-      admin: ADMIN_ADDRESS,
+      operator,
       globalPool,
-      tokenAccount,
-      tokenMint: nftMint,
-      tokenMintEdition: nftEdition,
-      tokenMintRecord,
-      mintMetadata,
-      authRules: MPL_DEFAULT_RULE_SET,
-      sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
-      signer: userAddress,
-      userPool: userPool,
-      tokenProgram: TOKEN_PROGRAM_ID,
-      tokenMetadataProgram: METAPLEX,
-      authRulesProgram: TOKEN_AUTH_RULES_ID,
+      configChange,
       systemProgram: SystemProgram.programId,
     })
     .transaction();
 
-  tx.add(txId);
+  return tx;
+};
 
-  tx.feePayer = userAddress;
-  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+/**
+ * Assign operator / pauser / treasurer role as admin
+ */
+export const setRoleTx = async (
+  admin: PublicKey,
+  role: Role,
+  newKey: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
 
-  const txData = await wallet.signTransaction(tx);
+  const tx = await program.methods
+    .setRole(role, newKey)
+    .accounts({
+      admin,
+      globalPool,
+    })
+    .transaction();
 
-  return txData.serialize({ requireAllSignatures: false });
+  return tx;
+};
+
+/**
+ * Deposit reward token to the vault as treasurer
+ */
+export const fundRewardVaultTx = async (
+  treasurer: PublicKey,
+  amount: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const rewardMint = await getRewardMint(globalPool, program);
+  const rewardVault = await getAssociatedTokenAccount(
+    globalPool,
+    rewardMint
+  );
+  const treasurerTokenAccount = await getAssociatedTokenAccount(
+    treasurer,
+    rewardMint
+  );
+
+  const tx = await program.methods
+    .fundRewardVault(new anchor.BN(amount))
+    .accounts({
+      treasurer,
+      globalPool,
+      rewardMint,
+      rewardVault,
+      treasurerTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Withdraw reward token from the vault as treasurer
+ */
+export const withdrawRewardVaultTx = async (
+  treasurer: PublicKey,
+  amount: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const rewardMint = await getRewardMint(globalPool, program);
+  const rewardVault = await getAssociatedTokenAccount(
+    globalPool,
+    rewardMint
+  );
+  const treasurerTokenAccount = await getAssociatedTokenAccount(
+    treasurer,
+    rewardMint
+  );
+
+  const tx = await program.methods
+    .withdrawRewardVault(new anchor.BN(amount))
+    .accounts({
+      treasurer,
+      globalPool,
+      rewardMint,
+      rewardVault,
+      treasurerTokenAccount,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Withdraw what is left in a legacy reward mint vault as treasurer, once its claim window is closed
+ * Pass the reward epoch of an older migration, its legacy mint is read from its migration archive
+ */
+export const withdrawLegacyVaultTx = async (
+  treasurer: PublicKey,
+  amount: number,
+  program: anchor.Program,
+  archivedEpoch: number | null = null
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const migrationArchive =
+    archivedEpoch === null ? null : getMigrationArchivePda(archivedEpoch, program);
+  const { migration } = migrationArchive
+    ? await program.account.migrationArchive.fetch(migrationArchive)
+    : await program.account.globalPool.fetch(globalPool);
+  const legacyMint = (migration as any).legacyMint as PublicKey;
+
+  const tx = await program.methods
+    .withdrawLegacyVault(new anchor.BN(amount))
+    .accounts({
+      treasurer,
+      globalPool,
+      migrationArchive,
+      legacyMint,
+      legacyVault: await getAssociatedTokenAccount(globalPool, legacyMint),
+      treasurerTokenAccount: await getAssociatedTokenAccount(treasurer, legacyMint),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Pause lock / claim / unlock with PAUSE_* flags as pauser
+ */
+export const pauseTx = async (
+  pauser: PublicKey,
+  flags: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .pause(flags)
+    .accounts({
+      pauser,
+      globalPool,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Clear PAUSE_* flags as admin
+ */
+export const unpauseTx = async (
+  admin: PublicKey,
+  flags: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .unpause(flags)
+    .accounts({
+      admin,
+      globalPool,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Turn emergency mode on / off as admin
+ */
+export const setEmergencyModeTx = async (
+  admin: PublicKey,
+  enabled: boolean,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .setEmergencyMode(enabled)
+    .accounts({
+      admin,
+      globalPool,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Initialize UserPool PDA
+ */
+export const createInitUserTx = async (
+  userAddress: PublicKey,
+  program: anchor.Program,
+  referrer: PublicKey | null = null
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  const tx = await program.methods
+    .initUser(referrer)
+    .accounts({
+      user: userAddress,
+      globalPool,
+      userPool,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * User pool of the user's referrer, null if the user wasn't referred
+ * Claims require it even if the referrer closed its pool
+ */
+export const getReferrerPool = async (
+  userPool: PublicKey,
+  program: anchor.Program
+): Promise<PublicKey | null> => {
+  const userPoolData = await program.account.userPool.fetch(userPool);
+  const referrer = userPoolData.referrer as PublicKey;
+  if (referrer.equals(PublicKey.default)) {
+    return null;
+  }
+
+  const [referrerPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    referrer.toBytes(),
+  ], program.programId);
+  return referrerPool;
+};
+
+/**
+ * Treasury receiving protocol fees, null if unset
+ */
+export const getTreasury = async (
+  globalPool: PublicKey,
+  program: anchor.Program
+): Promise<PublicKey | null> => {
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  const treasury = globalPoolData.treasury as PublicKey;
+  return treasury.equals(PublicKey.default) ? null : treasury;
+};
+
+/**
+ * Fetch the current reward mint, it changes once a reward mint migration is finalized
+ */
+export const getRewardMint = async (
+  globalPool: PublicKey,
+  program: anchor.Program
+): Promise<PublicKey> => {
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  return globalPoolData.rewardMint as PublicKey;
+};
+
+/**
+ * Queue treasury, claim fee bps and lock fee lamports change as operator
+ */
+export const setFeeConfigTx = async (
+  operator: PublicKey,
+  treasury: PublicKey,
+  claimFeeBps: number,
+  lockFee: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+  const [configChange] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CONFIG_CHANGE_SEED)],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .queueConfigChange(null, null, null, {
+      treasury,
+      claimFeeBps,
+      lockFee: new anchor.BN(lockFee),
+    }, null)
+    .accounts({
+      operator,
+      globalPool,
+      configChange,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Set referral bonus bps and the total bonus cap per referrer as operator
+ */
+export const setReferralConfigTx = async (
+  operator: PublicKey,
+  referralBps: number,
+  referralCap: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .setReferralConfig(referralBps, new anchor.BN(referralCap))
+    .accounts({
+      operator,
+      globalPool,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Remaining accounts for the hook CPI of lock / unlock / claim
+ * Empty if no hook program is set, extra accounts are passed through to the hook
+ */
+export const getHookAccounts = async (
+  globalPool: PublicKey,
+  program: anchor.Program,
+  extraAccounts: AccountMeta[] = []
+): Promise<AccountMeta[]> => {
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  const hookProgram = globalPoolData.hookProgram as PublicKey;
+  if (hookProgram.equals(PublicKey.default)) {
+    return [];
+  }
+  const [hookAuthority] = PublicKey.findProgramAddressSync(
+    [Buffer.from(HOOK_AUTHORITY_SEED)],
+    program.programId
+  );
+  return [
+    { pubkey: hookProgram, isSigner: false, isWritable: false },
+    { pubkey: hookAuthority, isSigner: false, isWritable: false },
+    ...extraAccounts,
+  ];
+};
+
+/**
+ * Staking receipt accounts of an NFT, receipt mint exists only while it's staked with a receipt
+ */
+export const getReceiptAccounts = (
+  owner: PublicKey,
+  nftMint: PublicKey,
+  program: anchor.Program
+) => {
+  const [receiptMint] = PublicKey.findProgramAddressSync(
+    [Buffer.from(RECEIPT_SEED), nftMint.toBytes()],
+    program.programId
+  );
+  const receiptAccount = getAssociatedTokenAddressSync(
+    receiptMint,
+    owner,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+  return {
+    receiptMint,
+    receiptAccount,
+    token2022Program: TOKEN_2022_PROGRAM_ID,
+  };
+};
+
+/**
+ * Stake creators PDA of an NFT, holds its creators' royalty
+ */
+export const getStakeCreatorsPda = (nftMint: PublicKey, program: anchor.Program) => {
+  const [stakeCreators] = PublicKey.findProgramAddressSync(
+    [Buffer.from(STAKE_CREATORS_SEED), nftMint.toBytes()],
+    program.programId
+  );
+  return stakeCreators;
+};
+
+/**
+ * Creator claims its royalty accrued on a staked NFT
+ */
+export const claimCreatorRoyaltyTx = async (
+  creator: PublicKey,
+  nftMint: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const stakeCreators = getStakeCreatorsPda(nftMint, program);
+  const stakeCreatorsData = await program.account.stakeCreators.fetch(stakeCreators);
+  const rewardMint = await getRewardMint(globalPool, program);
+
+  const tx = await program.methods
+    .claimCreatorRoyalty()
+    .accounts({
+      creator,
+      globalPool,
+      stakeCreators,
+      payer: stakeCreatorsData.payer as PublicKey,
+      rewardMint,
+      rewardVault: await getAssociatedTokenAccount(globalPool, rewardMint),
+      creatorRewardAccount: await getAssociatedTokenAccount(creator, rewardMint),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Queue the share of staking reward paid to NFT creators as operator
+ */
+export const setCreatorShareTx = async (
+  operator: PublicKey,
+  creatorShareBps: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+  const [configChange] = PublicKey.findProgramAddressSync(
+    [Buffer.from(CONFIG_CHANGE_SEED)],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .queueConfigChange(null, null, null, null, creatorShareBps)
+    .accounts({
+      operator,
+      globalPool,
+      configChange,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+export const createLockPnftTx = async (
+  wallet: Wallet,
+  nftMint: PublicKey,
+  program: anchor.Program,
+  connection: Connection,
+  withReceipt: boolean = false
+) => {
+  const userAddress = wallet.publicKey;
+
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  const nftEdition = await getMasterEdition(nftMint);
+
+  let tokenAccount = await getAssociatedTokenAccount(userAddress, nftMint);
+
+  const mintMetadata = await getMetadata(nftMint);
+
+  const tokenMintRecord = findTokenRecordPda(nftMint, tokenAccount);
+
+  const tx = new Transaction();
+
+  const txId = await program.methods
+    .lockPnft()
+    .accounts({
+      user: userAddress,
+      globalPool,
+      userPool: userPool,
+      tokenMint: nftMint,
+      tokenAccount,
+      tokenMintEdition: nftEdition,
+      tokenMintRecord,
+      mintMetadata,
+      authRules: MPL_DEFAULT_RULE_SET,
+      sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      tokenMetadataProgram: METAPLEX,
+      authRulesProgram: TOKEN_AUTH_RULES_ID,
+      systemProgram: SystemProgram.programId,
+      treasury: await getTreasury(globalPool, program),
+      stakeCreators:
+        (await program.account.globalPool.fetch(globalPool)).creatorShareBps > 0
+          ? getStakeCreatorsPda(nftMint, program)
+          : null,
+      ...(withReceipt
+        ? {
+            ...getReceiptAccounts(userAddress, nftMint, program),
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          }
+        : {
+            receiptMint: null,
+            receiptAccount: null,
+            token2022Program: null,
+            associatedTokenProgram: null,
+          }),
+    })
+    .remainingAccounts(await getHookAccounts(globalPool, program))
+    .transaction();
+
+  tx.add(txId);
+
+  tx.feePayer = userAddress;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+  const txData = await wallet.signTransaction(tx);
+
+  return txData.serialize({ requireAllSignatures: false });
+};
+
+export const claimRewardTx = async (
+  userAddress: PublicKey,
+  program: anchor.Program,
+  connection: Connection
+) => {
+  const [globalPool] = await PublicKey.findProgramAddress(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  // Points mode doesn't transfer reward token
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  if (globalPoolData.pointsMode) {
+    return await program.methods
+      .claimReward()
+      .accounts({
+        user: userAddress,
+        globalPool,
+        userPool: userPool,
+        rewardMint: globalPoolData.rewardMint as PublicKey,
+        rewardVault: null,
+        userRewardAccount: null,
+        treasuryRewardAccount: null,
+        ...(await getSeasonAccounts(userAddress, program)),
+        referrerPool: await getReferrerPool(userPool, program),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(await getHookAccounts(globalPool, program))
+      .transaction();
+  }
+
+  const rewardMint = globalPoolData.rewardMint as PublicKey;
+  let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
+    connection,
+    userAddress,
+    userAddress,
+    [rewardMint]
+  );
+
+  const rewardVault = await getAssociatedTokenAccount(
+    globalPool,
+    rewardMint
+  );
+
+  const treasury = await getTreasury(globalPool, program);
+
+  const txId = await program.methods
+    .claimReward()
+    .accounts({
+      user: userAddress,
+      globalPool,
+      userPool: userPool,
+      rewardMint,
+      rewardVault,
+      userRewardAccount: destinationAccounts[0],
+      treasuryRewardAccount: treasury
+        ? await getAssociatedTokenAccount(treasury, rewardMint)
+        : null,
+      ...(await getSeasonAccounts(userAddress, program)),
+      referrerPool: await getReferrerPool(userPool, program),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      SystemProgram: SystemProgram.programId
+    })
+    .remainingAccounts(await getHookAccounts(globalPool, program))
+    .preInstructions([...instructions])
+    .transaction();
+
+  return txId;
+};
+
+export const claimLegacyRewardTx = async (
+  userAddress: PublicKey,
+  program: anchor.Program,
+  connection: Connection
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  const legacyMint = (globalPoolData.migration as any).legacyMint as PublicKey;
+
+  let { instructions, destinationAccounts } = await getATokenAccountsNeedCreate(
+    connection,
+    userAddress,
+    userAddress,
+    [legacyMint]
+  );
+
+  const legacyVault = await getAssociatedTokenAccount(globalPool, legacyMint);
+
+  const tx = await program.methods
+    .claimLegacyReward()
+    .accounts({
+      user: userAddress,
+      globalPool,
+      userPool,
+      legacyMint,
+      legacyVault,
+      userLegacyAccount: destinationAccounts[0],
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .preInstructions([...instructions])
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Staked pNFT accounts shared by unlock, emergency unlock and force unlock
+ */
+export const getUnlockNftAccounts = async (
+  owner: PublicKey,
+  nftMint: PublicKey,
+  program: anchor.Program
+) => {
+  const tokenAccount = await getAssociatedTokenAccount(owner, nftMint);
+
+  return {
+    tokenMint: nftMint,
+    tokenAccount,
+    tokenMintEdition: await getMasterEdition(nftMint),
+    tokenMintRecord: findTokenRecordPda(nftMint, tokenAccount),
+    mintMetadata: await getMetadata(nftMint),
+    authRules: MPL_DEFAULT_RULE_SET,
+    sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+    tokenProgram: TOKEN_PROGRAM_ID,
+    tokenMetadataProgram: METAPLEX,
+    authRulesProgram: TOKEN_AUTH_RULES_ID,
+    ...getReceiptAccounts(owner, nftMint, program),
+    stakeCreators: getStakeCreatorsPda(nftMint, program),
+  };
+};
+
+export const createUnlockPnftTx = async (
+  wallet: Wallet,
+  nftMint: PublicKey,
+  program: anchor.Program,
+  connection: Connection
+) => {
+  const userAddress = wallet.publicKey;
+
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  const tx = new Transaction();
+
+  const txId = await program.methods
+    .unlockPnft()
+    .accounts({
+      user: userAddress,
+      globalPool,
+      userPool: userPool,
+      nft: await getUnlockNftAccounts(userAddress, nftMint, program),
+      systemProgram: SystemProgram.programId,
+      treasury: await getTreasury(globalPool, program),
+      ...(await getSeasonAccounts(userAddress, program)),
+    })
+    .remainingAccounts(await getHookAccounts(globalPool, program))
+    .transaction();
+
+  tx.add(txId);
+
+  tx.feePayer = userAddress;
+  tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
+
+  const txData = await wallet.signTransaction(tx);
+
+  return txData.serialize({ requireAllSignatures: false });
+};
+
+/**
+ * Unlock owner's pNFT in emergency mode, payer can be anyone
+ */
+export const createEmergencyUnlockTx = async (
+  payer: PublicKey,
+  owner: PublicKey,
+  nftMint: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    owner.toBytes(),
+  ], program.programId);
+
+  const tx = await program.methods
+    .emergencyUnlock()
+    .accounts({
+      payer,
+      owner,
+      globalPool,
+      userPool,
+      nft: await getUnlockNftAccounts(owner, nftMint, program),
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Unlock owner's pNFT as admin, its reward is settled to owner's pending reward
+ */
+export const createForceUnlockTx = async (
+  admin: PublicKey,
+  owner: PublicKey,
+  nftMint: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    owner.toBytes(),
+  ], program.programId);
+
+  const tx = await program.methods
+    .forceUnlock()
+    .accounts({
+      admin,
+      owner,
+      globalPool,
+      userPool,
+      nft: await getUnlockNftAccounts(owner, nftMint, program),
+      systemProgram: SystemProgram.programId,
+      ...(await getSeasonAccounts(owner, program)),
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Close UserPool PDA and reclaim its rent
+ */
+export const createCloseUserTx = async (
+  userAddress: PublicKey,
+  program: anchor.Program
+) => {
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .closeUserPool()
+    .accounts({
+      user: userAddress,
+      globalPool,
+      userPool,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Spend user's points for an off-chain redemption as operator
+ */
+export const spendPointsTx = async (
+  operator: PublicKey,
+  userAddress: PublicKey,
+  amount: number,
+  redemptionId: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  const tx = await program.methods
+    .spendPoints(new anchor.BN(amount), new anchor.BN(redemptionId))
+    .accounts({
+      operator,
+      globalPool,
+      userPool,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Season PDA of given id
+ */
+export const getSeasonPda = (season: number, program: anchor.Program) => {
+  const seasonId = Buffer.alloc(2);
+  seasonId.writeUInt16LE(season);
+  const [seasonPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from(SEASON_SEED), seasonId],
+    program.programId
+  );
+  return seasonPda;
+};
+
+/**
+ * UserSeason PDA of given season and user
+ */
+export const getUserSeasonPda = (
+  season: number,
+  userAddress: PublicKey,
+  program: anchor.Program
+) => {
+  const seasonId = Buffer.alloc(2);
+  seasonId.writeUInt16LE(season);
+  const [userSeason] = PublicKey.findProgramAddressSync(
+    [Buffer.from(USER_SEASON_SEED), seasonId, userAddress.toBytes()],
+    program.programId
+  );
+  return userSeason;
+};
+
+/**
+ * Season accounts for claim and unlock, null when no season is active
+ */
+export const getSeasonAccounts = async (
+  userAddress: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  if (!globalPoolData.seasonActive) {
+    return { season: null, userSeason: null };
+  }
+  const season = globalPoolData.pointsSeason as number;
+  return {
+    season: getSeasonPda(season, program),
+    userSeason: getUserSeasonPda(season, userAddress, program),
+  };
+};
+
+/**
+ * Start the next season as operator
+ */
+export const startSeasonTx = async (
+  operator: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+
+  const tx = await program.methods
+    .startSeason()
+    .accounts({
+      operator,
+      globalPool,
+      season: getSeasonPda(globalPoolData.pointsSeason as number, program),
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * End the active season as operator
+ */
+export const endSeasonTx = async (
+  operator: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+
+  const tx = await program.methods
+    .endSeason()
+    .accounts({
+      operator,
+      globalPool,
+      season: getSeasonPda(globalPoolData.pointsSeason as number, program),
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Settle user's reward accrued until the end of every season ended since the user's last checkpoint
+ * payer can be anyone, it pays for the UserSeason accounts
+ */
+export const settleUserSeasonTx = async (
+  payer: PublicKey,
+  userAddress: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  const globalPoolData = await program.account.globalPool.fetch(globalPool);
+  const userPoolData = await program.account.userPool.fetch(userPool);
+
+  const tx = new Transaction();
+  for (
+    let season = userPoolData.season as number;
+    season < (globalPoolData.pointsSeason as number);
+    season++
+  ) {
+    tx.add(
+      await program.methods
+        .settleUserSeason()
+        .accounts({
+          payer,
+          globalPool,
+          userPool,
+          season: getSeasonPda(season, program),
+          userSeason: getUserSeasonPda(season, userAddress, program),
+          systemProgram: SystemProgram.programId,
+        })
+        .transaction()
+    );
+  }
+
+  return tx;
+};
+
+/**
+ * Leaderboard of a season, UserSeason accounts sorted by reward descending
+ */
+export const getSeasonLeaderboard = async (
+  season: number,
+  program: anchor.Program
+): Promise<UserSeason[]> => {
+  const seasonId = Buffer.alloc(2);
+  seasonId.writeUInt16LE(season);
+
+  // season follows the 8 byte discriminator and 1 byte version
+  const accounts = await program.account.userSeason.all([
+    { memcmp: { offset: 9, bytes: anchor.utils.bytes.bs58.encode(seasonId) } },
+  ]);
+
+  return accounts
+    .map((account) => account.account as unknown as UserSeason)
+    .sort((a, b) => b.reward.cmp(a.reward));
+};
+
+/**
+ * Set hook program notified on lock / unlock / claim as admin, null turns it off
+ */
+export const setHookProgramTx = async (
+  admin: PublicKey,
+  hookProgram: PublicKey | null,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .setHookProgram(hookProgram)
+    .accounts({
+      admin,
+      globalPool,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Create voter weight record of a governing token owner for a realm
+ */
+export const createVoterWeightRecordTx = async (
+  payer: PublicKey,
+  governingTokenOwner: PublicKey,
+  governanceProgram: PublicKey,
+  realm: PublicKey,
+  realmGoverningTokenMint: PublicKey,
+  program: anchor.Program
+) => {
+  const [voterWeightRecord] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(VOTER_WEIGHT_SEED),
+      realm.toBytes(),
+      realmGoverningTokenMint.toBytes(),
+      governingTokenOwner.toBytes(),
+    ],
+    program.programId
+  );
+
+  const tx = await program.methods
+    .createVoterWeightRecord(governingTokenOwner)
+    .accounts({
+      payer,
+      voterWeightRecord,
+      governanceProgram,
+      realm,
+      realmGoverningTokenMint,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Refresh voter weight for an action other than voting,
+ * add it right before the governance instruction in the same transaction
+ */
+export const updateVoterWeightRecordTx = async (
+  governingTokenOwner: PublicKey,
+  realm: PublicKey,
+  realmGoverningTokenMint: PublicKey,
+  voterWeightAction: VoterWeightAction,
+  program: anchor.Program
+) => {
+  const [voterWeightRecord] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(VOTER_WEIGHT_SEED),
+      realm.toBytes(),
+      realmGoverningTokenMint.toBytes(),
+      governingTokenOwner.toBytes(),
+    ],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    governingTokenOwner.toBytes(),
+  ], program.programId);
+
+  const tx = await program.methods
+    .updateVoterWeightRecord(voterWeightAction)
+    .accounts({
+      voterWeightRecord,
+      userPool,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * NFT vote record PDA of a staked NFT on a proposal
+ */
+export const getNftVoteRecordPda = (
+  proposal: PublicKey,
+  nftMint: PublicKey,
+  program: anchor.Program
+) => {
+  const [nftVoteRecord] = PublicKey.findProgramAddressSync(
+    [Buffer.from(NFT_VOTE_RECORD_SEED), proposal.toBytes(), nftMint.toBytes()],
+    program.programId
+  );
+  return nftVoteRecord;
+};
+
+/**
+ * Set vote weight on a proposal from staked NFTs that haven't voted on it yet,
+ * add it right before the governance cast_vote in the same transaction
+ * Up to count items from offset are recorded, call again with the next offset for more
+ */
+export const castNftVoteTx = async (
+  governingTokenOwner: PublicKey,
+  realm: PublicKey,
+  realmGoverningTokenMint: PublicKey,
+  proposal: PublicKey,
+  program: anchor.Program,
+  offset: number = 0,
+  count: number = 20
+) => {
+  const [voterWeightRecord] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(VOTER_WEIGHT_SEED),
+      realm.toBytes(),
+      realmGoverningTokenMint.toBytes(),
+      governingTokenOwner.toBytes(),
+    ],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    governingTokenOwner.toBytes(),
+  ], program.programId);
+
+  const userPoolData = await program.account.userPool.fetch(userPool);
+  const items = (userPoolData.items as { nftAddr: PublicKey }[])
+    .slice(0, (userPoolData.itemCount as anchor.BN).toNumber())
+    .slice(offset, offset + count);
+
+  const tx = await program.methods
+    .castNftVote(proposal, offset)
+    .accounts({
+      governingTokenOwner,
+      voterWeightRecord,
+      userPool,
+      systemProgram: SystemProgram.programId,
+    })
+    .remainingAccounts(
+      items.map((item) => ({
+        pubkey: getNftVoteRecordPda(proposal, item.nftAddr, program),
+        isSigner: false,
+        isWritable: true,
+      }))
+    )
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Close owner's NFT vote records of a proposal that is no longer voting and refund their rent
+ */
+export const relinquishNftVoteTx = async (
+  governingTokenOwner: PublicKey,
+  realm: PublicKey,
+  realmGoverningTokenMint: PublicKey,
+  proposal: PublicKey,
+  program: anchor.Program
+) => {
+  const [voterWeightRecord] = PublicKey.findProgramAddressSync(
+    [
+      Buffer.from(VOTER_WEIGHT_SEED),
+      realm.toBytes(),
+      realmGoverningTokenMint.toBytes(),
+      governingTokenOwner.toBytes(),
+    ],
+    program.programId
+  );
+
+  // proposal follows the 8 byte discriminator and 1 byte version
+  const records = await program.account.nftVoteRecord.all([
+    { memcmp: { offset: 9, bytes: proposal.toBase58() } },
+    { memcmp: { offset: 9 + 32 + 32, bytes: governingTokenOwner.toBase58() } },
+  ]);
+
+  const tx = await program.methods
+    .relinquishNftVote()
+    .accounts({
+      governingTokenOwner,
+      voterWeightRecord,
+      realm,
+      proposal,
+    })
+    .remainingAccounts(
+      records.map((record) => ({
+        pubkey: record.publicKey,
+        isSigner: false,
+        isWritable: true,
+      }))
+    )
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Simulate get_pending_reward to get rewards claimable now
+ */
+export const getPendingReward = async (
+  userAddress: PublicKey,
+  program: anchor.Program,
+  offset: number = 0
+): Promise<PendingReward> => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  return await program.methods
+    .getPendingReward(offset)
+    .accounts({
+      globalPool,
+      userPool,
+    })
+    .view();
+};
+
+/**
+ * Migrate unversioned or version 1 GlobalPool PDA as admin
+ */
+export const migrateGlobalPoolTx = async (
+  admin: PublicKey,
+  maxRewardPerDay: number,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  // Legacy layouts can't be fetched, the reward mint follows the admin key and the daily rate
+  const { data } = await program.provider.connection.getAccountInfo(globalPool);
+  const offset = data.subarray(8, 40).equals(admin.toBuffer()) ? 48 : 49;
+  const rewardMint = new PublicKey(data.subarray(offset, offset + 32));
+
+  const tx = await program.methods
+    .migrateGlobalPool(new anchor.BN(maxRewardPerDay))
+    .accounts({
+      admin,
+      globalPool,
+      rewardMint,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Migrate unversioned or version 1 UserPool PDA, anyone can pay for it
+ */
+export const migrateUserPoolTx = async (
+  payer: PublicKey,
+  userAddress: PublicKey,
+  program: anchor.Program
+) => {
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  const tx = await program.methods
+    .migrateUserPool()
+    .accounts({
+      payer,
+      owner: userAddress,
+      userPool,
+      systemProgram: SystemProgram.programId,
+    })
+    .transaction();
+
+  return tx;
+};
+
+/**
+ * Drop stale entries from UserPool PDA as its owner
+ */
+export const createReconcileUserPoolTx = async (
+  userAddress: PublicKey,
+  program: anchor.Program
+) => {
+  const [globalPool] = PublicKey.findProgramAddressSync(
+    [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+    program.programId
+  );
+
+  const [userPool] = PublicKey.findProgramAddressSync([
+    Buffer.from(USER_POOL_SEED),
+    userAddress.toBytes(),
+  ], program.programId);
+
+  const userPoolData = await program.account.userPool.fetch(userPool);
+
+  const remainingAccounts = [];
+  for (const item of userPoolData.items as any[]) {
+    // Token account holding the NFT now, its token record tells whether it's still locked
+    const holders = await program.provider.connection.getTokenLargestAccounts(item.nftAddr);
+    const tokenAccount =
+      holders.value.find((holder) => holder.amount === "1")?.address ??
+      (await getAssociatedTokenAccount(userAddress, item.nftAddr));
+    const tokenMintRecord = findTokenRecordPda(item.nftAddr, tokenAccount);
+    remainingAccounts.push(
+      { pubkey: tokenAccount, isSigner: false, isWritable: false },
+      { pubkey: tokenMintRecord, isSigner: false, isWritable: false },
+    );
+  }
+
+  const tx = await program.methods
+    .reconcileUserPool()
+    .accounts({
+      user: userAddress,
+      globalPool,
+      userPool,
+    })
+    .remainingAccounts(remainingAccounts)
+    .transaction();
+
+  return tx;
 };
 
 /**
@@ -397,7 +1916,7 @@ export const getRewardVault = async (program: anchor.Program) => {
 
   const rewardVault = await getAssociatedTokenAccount(
     globalPool,
-    REWARD_TOKEN_MINT
+    await getRewardMint(globalPool, program)
   );
 
   const balance = await program.provider.connection.getTokenAccountBalance(rewardVault);
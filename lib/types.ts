@@ -2,25 +2,148 @@ import * as anchor from '@coral-xyz/anchor';
 import { PublicKey } from '@solana/web3.js'
 
 export interface GlobalPool {
+    version: number,
     admin: PublicKey,
     rewardPerDay: anchor.BN,
     rewardMint: PublicKey,
     rewardEnable: boolean,
     totalStakedCount: anchor.BN,
+    pendingAdmin: PublicKey,
+    operator: PublicKey,
+    pauser: PublicKey,
+    treasurer: PublicKey,
+    pauseFlags: number,
+    configDelay: anchor.BN,
+    emergencyMode: boolean,
+    rewardEpoch: number,
+    rewardLiability: anchor.BN,
+    liabilityTime: anchor.BN,
+    migration: RewardMintMigration,
+    rewardDecimals: number,
+    maxRewardPerDay: anchor.BN,
+    hookProgram: PublicKey,
+    pointsMode: boolean,
+    pointsSeason: number,
+    seasonActive: boolean,
+    referralBps: number,
+    referralCap: anchor.BN,
+    treasury: PublicKey,
+    claimFeeBps: number,
+    lockFee: anchor.BN,
+    creatorShareBps: number,
     extra: anchor.BN,
 }
 
+export interface Season {
+    version: number,
+    id: number,
+    startTime: anchor.BN,
+    endTime: anchor.BN,
+    active: boolean,
+    rewardPerDay: anchor.BN,
+    totalReward: anchor.BN,
+    participants: anchor.BN,
+    extra: anchor.BN,
+}
+
+export interface UserSeason {
+    version: number,
+    season: number,
+    owner: PublicKey,
+    reward: anchor.BN,
+}
+
+export interface StakeCreators {
+    version: number,
+    mint: PublicKey,
+    payer: PublicKey,
+    staked: boolean,
+    creatorShareBps: number,
+    royaltyTime: anchor.BN,
+    rewardEpoch: number,
+    creators: CreatorRoyalty[],
+}
+
+export interface CreatorRoyalty {
+    address: PublicKey,
+    share: number,
+    unpaid: anchor.BN,
+    legacy: anchor.BN,
+}
+
+export interface RewardMintMigration {
+    legacyMint: PublicKey,
+    newMint: PublicKey,
+    cutoff: anchor.BN,
+    claimDeadline: anchor.BN,
+    ratioNum: anchor.BN,
+    ratioDen: anchor.BN,
+    legacyOutstanding: anchor.BN,
+    active: boolean,
+}
+
+export interface ConfigChange {
+    version: number,
+    proposer: PublicKey,
+    newRewardEnable: boolean | null,
+    newRewardPerDay: anchor.BN | null,
+    newConfigDelay: anchor.BN | null,
+    newCreatorShareBps: number | null,
+    queuedAt: anchor.BN,
+    eta: anchor.BN,
+}
+
 export interface UserPool {
+    version: number,
     owner: PublicKey,
     itemCount: anchor.BN,
     rewardTime: anchor.BN,
     pendingReward: anchor.BN,
+    rewardEpoch: number,
+    legacyReward: anchor.BN,
+    points: anchor.BN,
+    pointsSpent: anchor.BN,
+    pointsSeason: number,
+    seasonPoints: anchor.BN,
+    lastSeasonPoints: anchor.BN,
+    referrer: PublicKey,
+    referralBonus: anchor.BN,
+    referralEarned: anchor.BN,
+    season: number,
+    seasonTime: anchor.BN,
     extra: anchor.BN,
     items: StakedNFT[],
 }
 
+export interface PendingReward {
+    now: anchor.BN,
+    itemCount: anchor.BN,
+    pendingReward: anchor.BN,
+    legacyReward: anchor.BN,
+    total: anchor.BN,
+    creatorRoyalty: anchor.BN,
+    claimFee: anchor.BN,
+    net: anchor.BN,
+    items: ItemReward[],
+}
+
+export interface ItemReward {
+    nftAddr: PublicKey,
+    reward: anchor.BN,
+}
+
 export interface StakedNFT {
     nftAddr: PublicKey,
     stakeTime: anchor.BN,
-    extra: anchor.BN,
-}
\ No newline at end of file
+    creatorShareBps: number,
+    extra: number[],
+}
+
+export type Role = { operator: {} } | { pauser: {} } | { treasurer: {} };
+
+// CastVote weight is set with cast_nft_vote instead
+export type VoterWeightAction =
+    | { commentProposal: {} }
+    | { createGovernance: {} }
+    | { createProposal: {} }
+    | { signOffProposal: {} };
@@ -1,9 +1,71 @@
+use anchor_lang::{prelude::*, solana_program::pubkey};
+
 pub const GLOBAL_AUTHORITY_SEED: &str = "global-authority";
 pub const USER_POOL_SEED: &str = "user-pool";
+pub const CONFIG_CHANGE_SEED: &str = "config-change";
+pub const VOTER_WEIGHT_SEED: &str = "voter-weight-record";
+pub const RECEIPT_SEED: &str = "receipt";
+pub const SEASON_SEED: &str = "season";
+pub const USER_SEASON_SEED: &str = "user-season";
+pub const STAKE_CREATORS_SEED: &str = "stake-creators";
+pub const HOOK_AUTHORITY_SEED: &str = "hook-authority";
+pub const MIGRATION_ARCHIVE_SEED: &str = "migration-archive";
+pub const NFT_VOTE_RECORD_SEED: &str = "nft-vote-record";
+
+// Token metadata field of the receipt holding the staked NFT mint
+pub const RECEIPT_ORIGINAL_MINT_FIELD: &str = "original_mint";
 
 pub const REWARD_TOKEN_MINT_PUBKEY: &str = "8L4oFzS978NxjPhuaGuK2CYeSm12S9XmFFy4x5ihiYjD";
+#[cfg(not(feature = "localnet"))]
 pub const COLLECTION_ADDRESS: &str = "pdw2LK3tHnGV2R67M8VXyKkBkHrBr6VKjG3C7jxjUK9";
+// Verified creator of the test pNFTs, its keypair is tests/fixtures/collection-authority.json
+#[cfg(feature = "localnet")]
+pub const COLLECTION_ADDRESS: &str = "CR28Qwb4VuecVc6WMFSDeRa7aqWhz6HFsGbjjEcXhfV7";
+
+pub const AUTH_RULES_PROGRAM_ID: Pubkey = pubkey!("auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg");
+
+// sighash("global:stake_hook"), anchor hook programs can name their handler stake_hook
+pub const STAKE_HOOK_DISCRIMINATOR: [u8; 8] = [56, 161, 18, 114, 107, 248, 175, 84];
+
+// Pause bitmask flags stored on global pool, they gate every user flow moving NFTs or reward
+// PAUSE_LOCK: lock_pnft
+// PAUSE_CLAIM: claim_reward, claim_legacy_reward, claim_creator_royalty, settle_reward_epoch, spend_points
+// PAUSE_UNLOCK: unlock_pnft, force_unlock, reconcile_user_pool. It can only be set in emergency mode
+// and is cleared when emergency mode ends, emergency_unlock is never paused so NFTs can always be recovered
+// Role and config instructions, views and governance records are not paused
+pub const PAUSE_LOCK: u8 = 1 << 0;
+pub const PAUSE_CLAIM: u8 = 1 << 1;
+pub const PAUSE_UNLOCK: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_LOCK | PAUSE_CLAIM | PAUSE_UNLOCK;
 
 pub const DEFAULT_STAKE_SIZE: usize = 3; //100;
 
 pub const DAY: i64 = 86400; // 60 * 60 * 24
+
+// Voter weight of one staked NFT (6 decimals), grows linearly up to 2x over VOTER_WEIGHT_MAX_DURATION
+pub const VOTER_WEIGHT_PER_NFT: u64 = 1_000_000;
+pub const VOTER_WEIGHT_MAX_DURATION: i64 = 365 * DAY;
+// spl-governance ProposalV2 state byte, after account_type, governance and governing_token_mint
+pub const PROPOSAL_STATE_OFFSET: usize = 65;
+pub const PROPOSAL_STATE_VOTING: u8 = 2;
+
+// Max per-NFT entries returned by get_pending_reward, keeps return data under 1024 bytes
+pub const MAX_REWARD_VIEW_ITEMS: usize = 20;
+
+pub const MIN_CONFIG_DELAY: i64 = DAY;
+pub const DEFAULT_CONFIG_DELAY: i64 = 2 * DAY;
+pub const MAX_CONFIG_DELAY: i64 = 30 * DAY;
+pub const MIN_MIGRATION_CLAIM_WINDOW: i64 = 7 * DAY;
+pub const MAX_MIGRATION_CLAIM_WINDOW: i64 = 90 * DAY;
+// Conversion ratio of a reward mint migration stays within 1 / MAX_MIGRATION_RATIO and MAX_MIGRATION_RATIO
+pub const MAX_MIGRATION_RATIO: u64 = 1_000;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_REFERRAL_BPS: u16 = 1_000;
+pub const MAX_CLAIM_FEE_BPS: u16 = 2_000;
+// Lamports, charged on every lock and unlock
+pub const MAX_LOCK_FEE: u64 = 100_000_000;
+pub const MAX_CREATOR_SHARE_BPS: u16 = 2_000;
+
+// Hard ceiling for max_reward_per_day, so a century of accrual still fits in u64
+pub const REWARD_PER_DAY_CEILING: u64 = u64::MAX / (100 * 365);
@@ -18,4 +18,146 @@ pub enum StakingError {
     InvalidNFTAddress,
     #[msg("Reward is disabled")]
     DisabledReward,
+    #[msg("Metadata account is not owned by token metadata program")]
+    InvalidMetadataOwner,
+    #[msg("Master edition address is invalid")]
+    InvalidEdition,
+    #[msg("Token record address is invalid")]
+    InvalidTokenRecord,
+    #[msg("Token metadata program id is invalid")]
+    InvalidTokenMetadataProgram,
+    #[msg("Auth rules program id is invalid")]
+    InvalidAuthRulesProgram,
+    #[msg("Sysvar instructions address is invalid")]
+    InvalidSysvarInstructions,
+    #[msg("NFT is already staked")]
+    AlreadyStaked,
+    #[msg("Token account is invalid")]
+    InvalidTokenAccount,
+    #[msg("Remaining accounts do not match staked items")]
+    InvalidRemainingAccounts,
+    #[msg("User still has staked NFTs")]
+    StakedNFTRemaining,
+    #[msg("User still has pending reward")]
+    PendingRewardRemaining,
+    #[msg("Pending admin address dismatch")]
+    InvalidPendingAdmin,
+    #[msg("No admin transfer is pending")]
+    NoPendingAdmin,
+    #[msg("Operator address dismatch")]
+    InvalidOperator,
+    #[msg("Pauser address dismatch")]
+    InvalidPauser,
+    #[msg("Treasurer address dismatch")]
+    InvalidTreasurer,
+    #[msg("Locking is paused")]
+    LockPaused,
+    #[msg("Claiming reward is paused")]
+    ClaimPaused,
+    #[msg("Unlocking is paused")]
+    UnlockPaused,
+    #[msg("Pause flags are invalid")]
+    InvalidPauseFlags,
+    #[msg("Config change has nothing to update")]
+    EmptyConfigChange,
+    #[msg("Config change is still timelocked")]
+    ConfigChangeNotReady,
+    #[msg("Config delay is out of range")]
+    InvalidConfigDelay,
+    #[msg("Program is in emergency mode")]
+    EmergencyMode,
+    #[msg("Program is not in emergency mode")]
+    NotEmergencyMode,
+    #[msg("Reward mint migration is in progress")]
+    MigrationInProgress,
+    #[msg("No reward mint migration is in progress")]
+    NoActiveMigration,
+    #[msg("Legacy reward claim window is closed")]
+    ClaimWindowClosed,
+    #[msg("Legacy reward claim window is still open")]
+    ClaimWindowOpen,
+    #[msg("Migration ratio or claim window is invalid")]
+    InvalidMigrationConfig,
+    #[msg("New reward mint is same as current one")]
+    SameRewardMint,
+    #[msg("Reward per day exceeds the cap")]
+    RewardPerDayTooHigh,
+    #[msg("Reward cap is invalid")]
+    InvalidRewardCap,
+    #[msg("Config change does not change anything")]
+    NoOpConfigChange,
+    #[msg("Reward can not be enabled with zero reward per day")]
+    ZeroRewardEnabled,
+    #[msg("Reward mint decimals mismatch")]
+    RewardMintDecimalsMismatch,
+    #[msg("Account version is not supported, migrate it first")]
+    InvalidAccountVersion,
+    #[msg("Account is already migrated")]
+    AlreadyMigrated,
+    #[msg("Reward mint address dismatch")]
+    InvalidRewardMint,
+    #[msg("NFT is not staked")]
+    NotStaked,
+    #[msg("Hook program is invalid")]
+    InvalidHookProgram,
+    #[msg("Receipt accounts are missing or invalid")]
+    InvalidReceiptAccounts,
+    #[msg("Not available in points mode")]
+    PointsMode,
+    #[msg("Only available in points mode")]
+    NotPointsMode,
+    #[msg("Insufficient points")]
+    InsufficientPoints,
+    #[msg("Reward vault accounts are missing")]
+    MissingRewardAccounts,
+    #[msg("Season is active")]
+    SeasonActive,
+    #[msg("No season is active")]
+    NoActiveSeason,
+    #[msg("Season accounts are missing")]
+    MissingSeasonAccounts,
+    #[msg("Referral bps exceeds the max")]
+    InvalidReferralConfig,
+    #[msg("User can not refer itself")]
+    SelfReferral,
+    #[msg("Fee config is invalid")]
+    InvalidFeeConfig,
+    #[msg("Treasury account is missing or invalid")]
+    InvalidTreasury,
+    #[msg("Creator share bps exceeds the max")]
+    InvalidCreatorShare,
+    #[msg("Stake creators account is missing or invalid")]
+    InvalidStakeCreators,
+    #[msg("No royalty to claim")]
+    NoRoyalty,
+    #[msg("Hook authority account is invalid")]
+    InvalidHookAuthority,
+    #[msg("Queued config change is a reward mint migration")]
+    MigrationQueued,
+    #[msg("No reward mint migration is queued")]
+    NoQueuedMigration,
+    #[msg("Rewards of skipped migrations must be settled with settle_reward_epoch first")]
+    RewardEpochNotSettled,
+    #[msg("Migration archive account is missing or invalid")]
+    InvalidMigrationArchive,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Referrer pool is missing or invalid")]
+    MissingReferrerPool,
+    #[msg("Unlocks can only be paused in emergency mode")]
+    UnlockPauseNeedsEmergencyMode,
+    #[msg("Ended seasons must be settled with settle_user_season first")]
+    SeasonNotSettled,
+    #[msg("CastVote weight must be set with cast_nft_vote")]
+    UseCastNftVote,
+    #[msg("NFT vote record is invalid")]
+    InvalidNftVoteRecord,
+    #[msg("Proposal doesn't belong to the realm's governance program")]
+    InvalidProposal,
+    #[msg("Proposal is still voting")]
+    ProposalVoting,
+    #[msg("User still has unspent points")]
+    PointsRemaining,
+    #[msg("Referrer who earned referral bonus can not close its user pool")]
+    ReferralEarned,
 }
@@ -0,0 +1,229 @@
+use crate::*;
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferAccepted {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct RoleUpdated {
+    pub role: Role,
+    pub old_key: Pubkey,
+    pub new_key: Pubkey,
+}
+
+#[event]
+pub struct RewardVaultFunded {
+    pub treasurer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RewardVaultWithdrawn {
+    pub treasurer: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub authority: Pubkey,
+    pub old_flags: u8,
+    pub new_flags: u8,
+}
+
+#[event]
+pub struct ConfigChangeQueued {
+    pub proposer: Pubkey,
+    pub new_reward_enable: Option<bool>,
+    pub new_reward_per_day: Option<u64>,
+    pub new_config_delay: Option<i64>,
+    pub new_fee_config: Option<FeeConfig>,
+    pub new_creator_share_bps: Option<u16>,
+    pub eta: i64,
+}
+
+#[event]
+pub struct ConfigChangeExecuted {
+    pub reward_enable: bool,
+    pub reward_per_day: u64,
+    pub config_delay: i64,
+}
+
+#[event]
+pub struct ConfigChangeCancelled {
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct EmergencyModeUpdated {
+    pub admin: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct EmergencyUnlocked {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub caller: Pubkey,
+}
+
+#[event]
+pub struct ForceUnlocked {
+    pub admin: Pubkey,
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub reward: u64,
+}
+
+#[event]
+pub struct RewardMintMigrationQueued {
+    pub proposer: Pubkey,
+    pub new_mint: Pubkey,
+    pub claim_window: i64,
+    pub ratio_num: u64,
+    pub ratio_den: u64,
+    pub eta: i64,
+}
+
+#[event]
+pub struct RewardMintMigrationStarted {
+    pub legacy_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub cutoff: i64,
+    pub claim_deadline: i64,
+    pub ratio_num: u64,
+    pub ratio_den: u64,
+    pub legacy_outstanding: u64,
+}
+
+#[event]
+pub struct RewardMintMigrationFinalized {
+    pub legacy_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub legacy_outstanding: u64,
+}
+
+#[event]
+pub struct LegacyRewardClaimed {
+    pub user: Pubkey,
+    pub legacy_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct HookProgramUpdated {
+    pub admin: Pubkey,
+    pub hook_program: Option<Pubkey>,
+}
+
+#[event]
+pub struct PointsAccrued {
+    pub user: Pubkey,
+    pub season: u16,
+    pub amount: u64,
+    pub points: u64,
+}
+
+#[event]
+pub struct PointsSpent {
+    pub operator: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub available: u64,
+    pub redemption_id: u64,
+}
+
+#[event]
+pub struct SeasonStarted {
+    pub operator: Pubkey,
+    pub season: u16,
+    pub start_time: i64,
+}
+
+#[event]
+pub struct SeasonEnded {
+    pub operator: Pubkey,
+    pub season: u16,
+    pub end_time: i64,
+    pub total_reward: u64,
+    pub participants: u64,
+}
+
+#[event]
+pub struct AccountMigrated {
+    pub account: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+pub struct ReferralConfigUpdated {
+    pub operator: Pubkey,
+    pub referral_bps: u16,
+    pub referral_cap: u64,
+}
+
+#[event]
+pub struct ReferralRewarded {
+    pub referrer: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub referral_earned: u64,
+}
+
+#[event]
+pub struct FeeConfigUpdated {
+    pub proposer: Pubkey,
+    pub treasury: Pubkey,
+    pub claim_fee_bps: u16,
+    pub lock_fee: u64,
+}
+
+#[event]
+pub struct RewardClaimed {
+    pub user: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub royalty: u64,
+    pub treasury: Pubkey,
+}
+
+#[event]
+pub struct LockFeeCollected {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub action: HookAction,
+    pub amount: u64,
+}
+
+#[event]
+pub struct CreatorShareUpdated {
+    pub proposer: Pubkey,
+    pub creator_share_bps: u16,
+}
+
+#[event]
+pub struct CreatorRoyaltyClaimed {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LegacyVaultWithdrawn {
+    pub treasurer: Pubkey,
+    pub legacy_mint: Pubkey,
+    pub amount: u64,
+}
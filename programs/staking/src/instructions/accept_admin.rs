@@ -0,0 +1,32 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(mut)]
+    pub pending_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = pending_admin @ StakingError::InvalidPendingAdmin,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl AcceptAdmin<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        let old_admin = global_pool.admin;
+        global_pool.admin = global_pool.pending_admin;
+        global_pool.pending_admin = Pubkey::default();
+
+        emit!(AdminTransferAccepted {
+            old_admin,
+            new_admin: global_pool.admin,
+        });
+
+        Ok(())
+    }
+}
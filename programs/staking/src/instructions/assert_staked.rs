@@ -0,0 +1,31 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct AssertStaked<'info> {
+    #[account(
+        seeds = [USER_POOL_SEED.as_ref(), user_pool.owner.as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+}
+
+/**
+ * Fail with NotStaked unless the mint is staked in the given user pool
+ * Callers should check the returned owner is the wallet they expect
+ */
+pub fn assert_staked_handler(ctx: Context<AssertStaked>, mint: Pubkey) -> Result<StakeStatus> {
+    let user_pool = &ctx.accounts.user_pool;
+
+    let item = user_pool
+        .items
+        .iter()
+        .take(user_pool.item_count as usize)
+        .find(|item| item.nft_addr.eq(&mint))
+        .ok_or(error!(StakingError::NotStaked))?;
+
+    Ok(StakeStatus {
+        owner: user_pool.owner,
+        nft_addr: item.nft_addr,
+        stake_time: item.stake_time,
+    })
+}
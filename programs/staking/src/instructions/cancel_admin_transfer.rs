@@ -0,0 +1,36 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = admin @ StakingError::InvalidAdmin,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl CancelAdminTransfer<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        require!(
+            !global_pool.pending_admin.eq(&Pubkey::default()),
+            StakingError::NoPendingAdmin
+        );
+
+        let pending_admin = global_pool.pending_admin;
+        global_pool.pending_admin = Pubkey::default();
+
+        emit!(AdminTransferCancelled {
+            admin: global_pool.admin,
+            pending_admin,
+        });
+
+        Ok(())
+    }
+}
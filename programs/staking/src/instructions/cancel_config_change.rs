@@ -0,0 +1,56 @@
+use {crate::*, anchor_lang::Discriminator};
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == global_pool.operator || authority.key() == global_pool.admin @ StakingError::InvalidOperator,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    /// CHECK: changes queued with an older layout can be cancelled too, checked in the handler
+    #[account(
+        mut,
+        seeds = [CONFIG_CHANGE_SEED.as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub config_change: UncheckedAccount<'info>,
+
+    /// CHECK: rent of the config change is refunded to its proposer
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+}
+
+impl CancelConfigChange<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let change_info = ctx.accounts.config_change.to_account_info();
+        {
+            let data = change_info.try_borrow_data()?;
+            require!(
+                data.len() > 41 && data[..8] == ConfigChange::DISCRIMINATOR,
+                StakingError::InvalidAccountVersion
+            );
+            // Every layout starts with the version byte and the proposer
+            require!(
+                data[9..41] == ctx.accounts.proposer.key().to_bytes(),
+                anchor_lang::error::ErrorCode::ConstraintHasOne
+            );
+        }
+
+        // Refund the rent to the proposer and close the change
+        close_account(&change_info, &ctx.accounts.proposer)?;
+
+        emit!(ConfigChangeCancelled {
+            authority: ctx.accounts.authority.key(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CastNftVote<'info> {
+    //  Owner pays for the NFT vote records and gets their rent back with relinquish_nft_vote
+    #[account(mut)]
+    pub governing_token_owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = governing_token_owner @ StakingError::InvalidOwner,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    #[account(
+        seeds = [USER_POOL_SEED.as_ref(), governing_token_owner.key().as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CastNftVote<'info> {
+    /**
+     * remaining_accounts are the NFT vote record PDAs of items[offset..], in order
+     * Items that already voted on the proposal add no weight, whichever wallet staked them then
+     * Weight accumulates over calls for the same proposal, so any number of items can vote
+     */
+    pub fn process_instruction(
+        ctx: &mut Context<'_, '_, '_, 'info, Self>,
+        proposal: Pubkey,
+        offset: u32,
+    ) -> Result<()> {
+        let user_pool = &ctx.accounts.user_pool;
+        let owner = ctx.accounts.governing_token_owner.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let clock = Clock::get()?;
+
+        let items = user_pool
+            .items
+            .iter()
+            .take(user_pool.item_count as usize)
+            .skip(offset as usize);
+        require!(ctx.remaining_accounts.len() <= items.len(), StakingError::InvalidNftVoteRecord);
+
+        let mut voter_weight: u64 = 0;
+        for (item, record_info) in items.zip(ctx.remaining_accounts) {
+            let (address, bump) = Pubkey::find_program_address(
+                &[NFT_VOTE_RECORD_SEED.as_bytes(), proposal.as_ref(), item.nft_addr.as_ref()],
+                &crate::ID,
+            );
+            require!(record_info.key().eq(&address), StakingError::InvalidNftVoteRecord);
+
+            // Already voted on this proposal
+            if record_info.owner.eq(&crate::ID) {
+                continue;
+            }
+
+            create_pda_account(
+                &owner,
+                record_info,
+                NftVoteRecord::DATA_SIZE,
+                &[NFT_VOTE_RECORD_SEED.as_bytes(), proposal.as_ref(), item.nft_addr.as_ref(), &[bump]],
+                &system_program,
+            )?;
+            NftVoteRecord {
+                version: NftVoteRecord::VERSION,
+                proposal,
+                nft_mint: item.nft_addr,
+                governing_token_owner: owner.key(),
+            }
+            .try_serialize(&mut &mut record_info.data.borrow_mut()[..])?;
+
+            voter_weight = voter_weight
+                .checked_add(user_pool.item_voter_weight(item, clock.unix_timestamp))
+                .unwrap();
+        }
+
+        let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+        if voter_weight_record.weight_action == Some(VoterWeightAction::CastVote)
+            && voter_weight_record.weight_action_target == Some(proposal)
+        {
+            voter_weight = voter_weight_record.voter_weight.checked_add(voter_weight).unwrap();
+        }
+
+        voter_weight_record.voter_weight = voter_weight;
+        voter_weight_record.voter_weight_expiry = Some(clock.slot);
+        voter_weight_record.weight_action = Some(VoterWeightAction::CastVote);
+        voter_weight_record.weight_action_target = Some(proposal);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use {
+    crate::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{self, Mint, Token, TokenAccount, Transfer},
+    },
+};
+
+#[derive(Accounts)]
+pub struct ClaimCreatorRoyalty<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = reward_mint,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_CREATORS_SEED.as_ref(), stake_creators.mint.as_ref()],
+        bump,
+    )]
+    pub stake_creators: Account<'info, Current<StakeCreators>>,
+
+    /// CHECK: receives the rent once stake creators is closed
+    #[account(
+        mut,
+        address = stake_creators.payer @ StakingError::InvalidStakeCreators,
+    )]
+    pub payer: AccountInfo<'info>,
+
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = global_pool,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        associated_token::mint = reward_mint,
+        associated_token::authority = creator,
+        payer = creator,
+    )]
+    pub creator_reward_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * Creator claims its royalty accrued on one NFT, until now while it's staked
+ * Stake creators is closed to its payer once the NFT is unlocked and every royalty is paid
+ */
+pub fn claim_creator_royalty_handler(ctx: Context<ClaimCreatorRoyalty>) -> Result<()> {
+    let global_pool = &mut ctx.accounts.global_pool;
+    let stake_creators = &mut ctx.accounts.stake_creators;
+
+    require!(!global_pool.is_paused(PAUSE_CLAIM), StakingError::ClaimPaused);
+    require!(!global_pool.migration.active, StakingError::MigrationInProgress);
+
+    // Royalty accrued before a migration cutoff is converted first, the rest accrues until now
+    let now = Clock::get()?.unix_timestamp;
+    stake_creators.settle_reward_epoch(global_pool)?;
+    stake_creators.accrue(now, global_pool.reward_per_day);
+
+    let creator = stake_creators
+        .creators
+        .iter_mut()
+        .find(|creator| creator.address.eq(&ctx.accounts.creator.key()))
+        .ok_or(error!(StakingError::InvalidStakeCreators))?;
+    let amount = creator.unpaid;
+    require!(amount > 0, StakingError::NoRoyalty);
+    creator.unpaid = 0;
+
+    // Validate reward vault balance enough
+    require!(
+        ctx.accounts.reward_vault.amount >= amount,
+        StakingError::LackVaultBalance
+    );
+
+    global_pool.accrue_liability(now);
+    global_pool.reward_liability = global_pool.reward_liability.saturating_sub(amount);
+
+    let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.global_pool]];
+    let signer = &[&seeds[..]];
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.reward_vault.to_account_info(),
+        to: ctx.accounts.creator_reward_account.to_account_info(),
+        authority: global_pool.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer),
+        amount,
+    )?;
+
+    emit!(CreatorRoyaltyClaimed {
+        creator: ctx.accounts.creator.key(),
+        mint: stake_creators.mint,
+        amount,
+    });
+
+    if !stake_creators.staked && stake_creators.unpaid() == 0 {
+        stake_creators.close(ctx.accounts.payer.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use {
+    crate::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{self, Mint, Token, TokenAccount, Transfer},
+    },
+};
+
+#[derive(Accounts)]
+pub struct ClaimLegacyReward<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+
+    #[account(address = global_pool.migration.legacy_mint)]
+    pub legacy_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = legacy_mint,
+        associated_token::authority = global_pool,
+    )]
+    pub legacy_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        associated_token::mint = legacy_mint,
+        associated_token::authority = user,
+        payer = user,
+    )]
+    pub user_legacy_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * Claim rewards accrued before migration cutoff in legacy mint
+ * Only available until the claim deadline of the active migration
+ */
+pub fn claim_legacy_reward_handler(ctx: Context<ClaimLegacyReward>) -> Result<()> {
+    let global_pool = &mut ctx.accounts.global_pool;
+    let user_pool = &mut ctx.accounts.user_pool;
+
+    require!(!global_pool.is_paused(PAUSE_CLAIM), StakingError::ClaimPaused);
+    require!(global_pool.migration.active, StakingError::NoActiveMigration);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    require!(
+        timestamp <= global_pool.migration.claim_deadline,
+        StakingError::ClaimWindowClosed
+    );
+
+    user_pool.settle_reward_epoch(global_pool)?;
+
+    let reward = user_pool.legacy_reward;
+    msg!("Legacy Reward: {}", reward);
+
+    // Validate legacy vault balance enough
+    require!(
+        ctx.accounts.legacy_vault.amount >= reward,
+        StakingError::LackVaultBalance
+    );
+
+    user_pool.legacy_reward = 0;
+    global_pool.migration.legacy_outstanding =
+        global_pool.migration.legacy_outstanding.saturating_sub(reward);
+
+    let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.global_pool]];
+    let signer = &[&seeds[..]];
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.legacy_vault.to_account_info(),
+        to: ctx.accounts.user_legacy_account.to_account_info(),
+        authority: ctx.accounts.global_pool.to_account_info(),
+    };
+    token::transfer(
+        CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer),
+        reward,
+    )?;
+
+    emit!(LegacyRewardClaimed {
+        user: ctx.accounts.user.key(),
+        legacy_mint: ctx.accounts.legacy_mint.key(),
+        amount: reward,
+    });
+
+    Ok(())
+}
@@ -17,23 +17,24 @@ pub struct ClaimReward<'info> {
         bump,
         has_one = reward_mint,
     )]
-    pub global_pool: Account<'info, GlobalPool>,
+    pub global_pool: Account<'info, Current<GlobalPool>>,
 
     #[account(
         mut,
         seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
-        bump
+        bump,
     )]
-    pub user_pool: Account<'info, UserPool>,
+    pub user_pool: Account<'info, Current<UserPool>>,
 
     pub reward_mint: Account<'info, Mint>,
 
+    //  Reward vault accounts are not needed in points mode
     #[account(
         mut,
         associated_token::mint = reward_mint,
         associated_token::authority = global_pool,
     )]
-    pub reward_vault: Account<'info, TokenAccount>,
+    pub reward_vault: Option<Account<'info, TokenAccount>>,
 
     #[account(
         init_if_needed,
@@ -41,44 +42,200 @@ pub struct ClaimReward<'info> {
         associated_token::authority = user,
         payer = user,
     )]
-    pub user_reward_account: Account<'info, TokenAccount>,
+    pub user_reward_account: Option<Account<'info, TokenAccount>>,
+
+    //  Treasury's reward token account, required only if claim fee is set
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        constraint = treasury_reward_account.owner == global_pool.treasury @ StakingError::InvalidTreasury,
+    )]
+    pub treasury_reward_account: Option<Account<'info, TokenAccount>>,
+
+    //  Season accounts are required while a season is active
+    #[account(
+        mut,
+        seeds = [SEASON_SEED.as_ref(), global_pool.points_season.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub season: Option<Account<'info, Current<Season>>>,
+
+    #[account(
+        init_if_needed,
+        space = UserSeason::DATA_SIZE,
+        seeds = [USER_SEASON_SEED.as_ref(), global_pool.points_season.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+    )]
+    pub user_season: Option<Account<'info, UserSeason>>,
+
+    /// CHECK: referrer's user pool, required if the user was referred
+    /// referral bonus is skipped if the referrer closed it or has to migrate or settle it first
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user_pool.referrer.as_ref()],
+        bump,
+    )]
+    pub referrer_pool: Option<UncheckedAccount<'info>>,
 
     pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn claim_reward_handler(ctx: Context<ClaimReward>) -> Result<()> {
+pub fn claim_reward_handler<'info>(ctx: Context<'_, '_, '_, 'info, ClaimReward<'info>>) -> Result<()> {
     let global_pool = &mut ctx.accounts.global_pool;
     let user_pool = &mut ctx.accounts.user_pool;
 
+    require!(!global_pool.is_paused(PAUSE_CLAIM), StakingError::ClaimPaused);
+
+    // Rewards are paid in new mint only after the migration is finalized
+    require!(!global_pool.migration.active, StakingError::MigrationInProgress);
+
     // Validate if reward enabled
     require!(
-        global_pool.reward_enable == true,
+        global_pool.reward_enable,
         StakingError::DisabledReward
     );
 
+    // Settle rewards accrued before the last reward mint migration
+    user_pool.settle_reward_epoch(global_pool)?;
+
     let timestamp = Clock::get()?.unix_timestamp;
-    let reward: u64 = user_pool.claim_reward(timestamp, global_pool.reward_per_day)?;
+
+    // Only reward accrued since season start counts toward the season
+    checkpoint_season(
+        global_pool,
+        user_pool,
+        ctx.accounts.season.as_deref_mut().map(|season| &mut **season),
+        ctx.accounts.user_season.as_deref_mut(),
+        timestamp,
+    )?;
+
+    // Creators' royalty captured at lock is kept out of each item's reward, they accrue it on their own
+    let royalty = user_pool.accrued_royalty(timestamp, global_pool.reward_per_day);
+
+    let referral_bonus = user_pool.referral_bonus;
+    let reward: u64 = user_pool
+        .claim_reward(timestamp, global_pool.reward_per_day)?;
+    global_pool.accrue_liability(timestamp);
     msg!("Reward: {}", reward);
 
-    // Validate reward vault balance enough
-    require!(
-        ctx.accounts.reward_vault.amount > reward,
-        StakingError::LackVaultBalance
-    );
+    // Referral bonus comes on top of the reward and isn't paid on referral bonus itself
+    if !user_pool.referrer.eq(&Pubkey::default()) {
+        let referrer_pool = ctx
+            .accounts
+            .referrer_pool
+            .as_ref()
+            .ok_or(error!(StakingError::MissingReferrerPool))?;
+        if !referrer_pool.data_is_empty() {
+            require!(referrer_pool.owner.eq(&crate::ID), StakingError::MissingReferrerPool);
+        }
+
+        // Referrers on an older layout or behind a skipped reward mint migration get no bonus
+        // until they migrate or settle, the user's claim doesn't wait for them
+        let current_layout = referrer_pool.data.borrow().get(8) == Some(&UserPool::VERSION);
+        if current_layout {
+            let mut referrer = Current::<UserPool>::try_deserialize(&mut &referrer_pool.data.borrow()[..])?;
+
+            // Bonus is credited in the current reward epoch, so earlier rewards are moved to legacy first
+            if referrer.settle_reward_epoch(global_pool).is_ok() {
+                let bonus = global_pool.referral_bonus(reward.saturating_sub(referral_bonus));
+                let credited = referrer.credit_referral(bonus, global_pool.referral_cap);
+                referrer.try_serialize(&mut &mut referrer_pool.data.borrow_mut()[..])?;
+                if credited > 0 {
+                    if !global_pool.points_mode {
+                        global_pool.reward_liability = global_pool.reward_liability.saturating_add(credited);
+                    }
+
+                    emit!(ReferralRewarded {
+                        referrer: referrer.owner,
+                        user: user_pool.owner,
+                        amount: credited,
+                        referral_earned: referrer.referral_earned,
+                    });
+                }
+            }
+        }
+    }
+
+    if global_pool.points_mode {
+        // Points are redeemed off-chain, nothing leaves the vault
+        user_pool.add_points(reward, global_pool.points_season);
+
+        emit!(PointsAccrued {
+            user: user_pool.owner,
+            season: global_pool.points_season,
+            amount: reward,
+            points: user_pool.points,
+        });
+    } else {
+        global_pool.reward_liability = global_pool.reward_liability.saturating_sub(reward);
+
+        let (Some(reward_vault), Some(user_reward_account)) =
+            (&ctx.accounts.reward_vault, &ctx.accounts.user_reward_account)
+        else {
+            return err!(StakingError::MissingRewardAccounts);
+        };
+
+        // Validate reward vault balance enough
+        require!(
+            reward_vault.amount > reward,
+            StakingError::LackVaultBalance
+        );
+
+        // Claim fee is split from the reward and goes to the treasury
+        let fee = global_pool.claim_fee(reward);
+        let amount = reward.checked_sub(fee).ok_or(error!(StakingError::MathOverflow))?;
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.global_pool]];
+        let signer = &[&seeds[..]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: reward_vault.to_account_info(),
+            to: user_reward_account.to_account_info(),
+            authority: ctx.accounts.global_pool.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer),
+            amount,
+        )?;
+
+        if fee > 0 {
+            let treasury_reward_account = ctx
+                .accounts
+                .treasury_reward_account
+                .as_ref()
+                .ok_or(error!(StakingError::InvalidTreasury))?;
+            let cpi_accounts = Transfer {
+                from: reward_vault.to_account_info(),
+                to: treasury_reward_account.to_account_info(),
+                authority: ctx.accounts.global_pool.to_account_info(),
+            };
+            token::transfer(
+                CpiContext::new_with_signer(token_program, cpi_accounts, signer),
+                fee,
+            )?;
+        }
+
+        emit!(RewardClaimed {
+            user: ctx.accounts.user.key(),
+            amount,
+            fee,
+            royalty,
+            treasury: ctx.accounts.global_pool.treasury,
+        });
+    }
 
-    let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.global_pool]];
-    let signer = &[&seeds[..]];
-    let token_program = ctx.accounts.token_program.to_account_info();
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.reward_vault.to_account_info(),
-        to: ctx.accounts.user_reward_account.to_account_info(),
-        authority: ctx.accounts.global_pool.to_account_info(),
-    };
-    token::transfer(
-        CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer),
-        reward,
+    invoke_stake_hook(
+        &ctx.accounts.global_pool,
+        ctx.remaining_accounts,
+        StakeHookArgs {
+            owner: ctx.accounts.user.key(),
+            mint: Pubkey::default(),
+            action: HookAction::Claim,
+            timestamp,
+        },
     )?;
 
     Ok(())
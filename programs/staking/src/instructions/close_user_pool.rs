@@ -0,0 +1,48 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct CloseUserPool<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    //  User pool is closed and its rent refunded to user
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
+        bump,
+        close = user,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+}
+
+impl CloseUserPool<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let user = &ctx.accounts.user_pool;
+
+        // Check user pool owner matched with signed user
+        require!(user.owner.eq(&ctx.accounts.user.key()), StakingError::InvalidOwner);
+
+        // All NFTs should be unlocked and rewards claimed before closing
+        require!(user.item_count == 0, StakingError::StakedNFTRemaining);
+        require!(
+            user.pending_reward == 0 && user.legacy_reward == 0,
+            StakingError::PendingRewardRemaining
+        );
+
+        // Nothing kept on the pool is lost: points, season accrual and the referral cap of a referrer
+        require!(user.available_points() == 0, StakingError::PointsRemaining);
+        require!(
+            user.season == ctx.accounts.global_pool.points_season,
+            StakingError::SeasonNotSettled
+        );
+        require!(user.referral_earned == 0, StakingError::ReferralEarned);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use {crate::*, anchor_spl::token::Mint};
+
+#[derive(Accounts)]
+#[instruction(governing_token_owner: Pubkey)]
+pub struct CreateVoterWeightRecord<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        space = VoterWeightRecord::DATA_SIZE,
+        seeds = [
+            VOTER_WEIGHT_SEED.as_ref(),
+            realm.key().as_ref(),
+            realm_governing_token_mint.key().as_ref(),
+            governing_token_owner.as_ref(),
+        ],
+        bump,
+        payer = payer
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// CHECK: executable, owner of the realm
+    #[account(executable)]
+    pub governance_program: AccountInfo<'info>,
+
+    /// CHECK: owned by the governance program, which checks the record's realm on use
+    #[account(owner = governance_program.key())]
+    pub realm: AccountInfo<'info>,
+
+    pub realm_governing_token_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_voter_weight_record_handler(
+    ctx: Context<CreateVoterWeightRecord>,
+    governing_token_owner: Pubkey,
+) -> Result<()> {
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+
+    voter_weight_record.realm = ctx.accounts.realm.key();
+    voter_weight_record.governing_token_mint = ctx.accounts.realm_governing_token_mint.key();
+    voter_weight_record.governing_token_owner = governing_token_owner;
+
+    // Expired until the first update
+    voter_weight_record.voter_weight_expiry = Some(0);
+
+    Ok(())
+}
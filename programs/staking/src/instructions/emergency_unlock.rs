@@ -0,0 +1,84 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct EmergencyUnlock<'info> {
+    //  Anyone can crank the unlock, NFT always goes back to its owner
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner of the user pool, receives the refunded rent
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+
+    pub nft: UnlockNftAccounts<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/**
+ * Unlock pNFT back to its owner without touching reward math
+ * Only available in emergency mode and ignores pause flags, PAUSE_UNLOCK included
+ */
+pub fn emergency_unlock_handler(ctx: Context<EmergencyUnlock>) -> Result<()> {
+    let global_pool = &mut ctx.accounts.global_pool;
+    let user_pool = &mut ctx.accounts.user_pool;
+    let nft = &ctx.accounts.nft;
+
+    require!(global_pool.emergency_mode, StakingError::NotEmergencyMode);
+
+    // Check user pool owner matched with token owner
+    require!(user_pool.owner.eq(&ctx.accounts.owner.key()), StakingError::InvalidOwner);
+
+    // Global authority revokes its own staking delegate, so owner signature isn't needed
+    nft.unlock(
+        &global_pool.to_account_info(),
+        ctx.bumps.global_pool,
+        &ctx.accounts.owner,
+        &global_pool.to_account_info(),
+        ctx.accounts.payer.as_ref(),
+        ctx.accounts.system_program.as_ref(),
+    )?;
+
+    // Validate if the nft staking exist and remove it
+    user_pool.drop_nft(nft.token_mint.key())?;
+    // Stake creators are marked unlocked, no reward is settled so no royalty is credited
+    settle_stake_creators(global_pool, &nft.stake_creators, None, Some(&ctx.accounts.owner))?;
+
+    global_pool.accrue_liability(Clock::get()?.unix_timestamp);
+    global_pool.total_staked_count -= 1;
+
+    nft.burn_receipt_if_any(
+        global_pool.to_account_info(),
+        ctx.bumps.global_pool,
+        ctx.bumps.nft.receipt_mint,
+        ctx.accounts.owner.to_account_info(),
+    )?;
+
+    shrink_user_pool(
+        user_pool.to_account_info(),
+        user_pool.item_count,
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    emit!(EmergencyUnlocked {
+        owner: ctx.accounts.owner.key(),
+        mint: nft.token_mint.key(),
+        caller: ctx.accounts.payer.key(),
+    });
+
+    Ok(())
+}
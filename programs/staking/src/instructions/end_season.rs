@@ -0,0 +1,51 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct EndSeason<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = operator @ StakingError::InvalidOperator,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [SEASON_SEED.as_ref(), global_pool.points_season.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub season: Account<'info, Current<Season>>,
+}
+
+impl EndSeason<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+        let season = &mut ctx.accounts.season;
+
+        require!(global_pool.season_active && season.active, StakingError::NoActiveSeason);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        season.end_time = timestamp;
+        season.active = false;
+
+        // Next season gets the next id, user season points roll over lazily on their next claim
+        // and reward accrued since each user's last checkpoint is counted by settle_user_season
+        global_pool.season_active = false;
+        global_pool.points_season = global_pool.points_season.checked_add(1).unwrap();
+
+        emit!(SeasonEnded {
+            operator: global_pool.operator,
+            season: season.id,
+            end_time: timestamp,
+            total_reward: season.total_reward,
+            participants: season.participants,
+        });
+
+        Ok(())
+    }
+}
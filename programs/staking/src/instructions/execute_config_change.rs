@@ -0,0 +1,73 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_CHANGE_SEED.as_ref()],
+        bump,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub config_change: Account<'info, Current<ConfigChange>>,
+
+    /// CHECK: rent of the config change is refunded to its proposer
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+}
+
+impl ExecuteConfigChange<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+        let config_change = &ctx.accounts.config_change;
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= config_change.eta, StakingError::ConfigChangeNotReady);
+        require!(config_change.new_migration.is_none(), StakingError::MigrationQueued);
+
+        // Settle liability with the old rate before it changes
+        global_pool.accrue_liability(now);
+
+        global_pool.reward_enable = config_change.new_reward_enable.unwrap_or(global_pool.reward_enable);
+        global_pool.reward_per_day = config_change.new_reward_per_day.unwrap_or(global_pool.reward_per_day);
+        global_pool.config_delay = config_change.new_config_delay.unwrap_or(global_pool.config_delay);
+        if let Some(fee_config) = config_change.new_fee_config {
+            global_pool.treasury = fee_config.treasury;
+            global_pool.claim_fee_bps = fee_config.claim_fee_bps;
+            global_pool.lock_fee = fee_config.lock_fee;
+
+            emit!(FeeConfigUpdated {
+                proposer: config_change.proposer,
+                treasury: fee_config.treasury,
+                claim_fee_bps: fee_config.claim_fee_bps,
+                lock_fee: fee_config.lock_fee,
+            });
+        }
+        if let Some(creator_share_bps) = config_change.new_creator_share_bps {
+            global_pool.creator_share_bps = creator_share_bps;
+
+            emit!(CreatorShareUpdated {
+                proposer: config_change.proposer,
+                creator_share_bps,
+            });
+        }
+
+        emit!(ConfigChangeExecuted {
+            reward_enable: global_pool.reward_enable,
+            reward_per_day: global_pool.reward_per_day,
+            config_delay: global_pool.config_delay,
+        });
+
+        Ok(())
+    }
+}
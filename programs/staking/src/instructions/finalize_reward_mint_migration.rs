@@ -0,0 +1,37 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct FinalizeRewardMintMigration<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl FinalizeRewardMintMigration<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        require!(global_pool.migration.active, StakingError::NoActiveMigration);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now > global_pool.migration.claim_deadline, StakingError::ClaimWindowOpen);
+
+        // Unclaimed legacy rewards are converted to new mint when each user settles
+        global_pool.reward_mint = global_pool.migration.new_mint;
+        global_pool.migration.active = false;
+
+        emit!(RewardMintMigrationFinalized {
+            legacy_mint: global_pool.migration.legacy_mint,
+            new_mint: global_pool.migration.new_mint,
+            legacy_outstanding: global_pool.migration.legacy_outstanding,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ForceUnlock<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: owner of the user pool, receives the refunded rent
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = admin @ StakingError::InvalidAdmin,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+
+    pub nft: UnlockNftAccounts<'info>,
+    pub system_program: Program<'info, System>,
+
+    //  Season accounts are required while a season is active
+    #[account(
+        mut,
+        seeds = [SEASON_SEED.as_ref(), global_pool.points_season.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub season: Option<Account<'info, Current<Season>>>,
+
+    #[account(
+        init_if_needed,
+        space = UserSeason::DATA_SIZE,
+        seeds = [USER_SEASON_SEED.as_ref(), global_pool.points_season.to_le_bytes().as_ref(), owner.key().as_ref()],
+        bump,
+        payer = admin,
+    )]
+    pub user_season: Option<Account<'info, UserSeason>>,
+}
+
+/**
+ * Admin unlocks a pNFT back to its owner and settles its reward to owner's pending reward
+ * NFT never leaves owner's token account
+ */
+pub fn force_unlock_handler(ctx: Context<ForceUnlock>) -> Result<()> {
+    let global_pool = &mut ctx.accounts.global_pool;
+    let user_pool = &mut ctx.accounts.user_pool;
+    let nft = &ctx.accounts.nft;
+
+    require!(!global_pool.is_paused(PAUSE_UNLOCK), StakingError::UnlockPaused);
+
+    // Check user pool owner matched with token owner
+    require!(user_pool.owner.eq(&ctx.accounts.owner.key()), StakingError::InvalidOwner);
+
+    // Settle rewards accrued before the last reward mint migration
+    user_pool.settle_reward_epoch(global_pool)?;
+
+    // Global authority revokes its own staking delegate, so owner signature isn't needed
+    nft.unlock(
+        &global_pool.to_account_info(),
+        ctx.bumps.global_pool,
+        &ctx.accounts.owner,
+        &global_pool.to_account_info(),
+        ctx.accounts.admin.as_ref(),
+        ctx.accounts.system_program.as_ref(),
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    // Count the season accrual of the item before it's removed
+    checkpoint_season(
+        global_pool,
+        user_pool,
+        ctx.accounts.season.as_deref_mut().map(|season| &mut **season),
+        ctx.accounts.user_season.as_deref_mut(),
+        timestamp,
+    )?;
+
+    // Validate if the nft staking exist and remove it
+    let reward = user_pool.remove_nft(
+        nft.token_mint.key(),
+        timestamp,
+        global_pool.reward_per_day
+    )?;
+
+    // Creators' royalty was kept out of the settled reward, it's credited to them until now
+    settle_stake_creators(global_pool, &nft.stake_creators, Some(timestamp), Some(&ctx.accounts.owner))?;
+
+    global_pool.accrue_liability(timestamp);
+    global_pool.total_staked_count -= 1;
+
+    nft.burn_receipt_if_any(
+        global_pool.to_account_info(),
+        ctx.bumps.global_pool,
+        ctx.bumps.nft.receipt_mint,
+        ctx.accounts.owner.to_account_info(),
+    )?;
+
+    shrink_user_pool(
+        user_pool.to_account_info(),
+        user_pool.item_count,
+        ctx.accounts.owner.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    emit!(ForceUnlocked {
+        admin: ctx.accounts.admin.key(),
+        owner: ctx.accounts.owner.key(),
+        mint: nft.token_mint.key(),
+        reward,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use {
+    crate::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{self, Mint, Token, TokenAccount, Transfer},
+    },
+};
+
+#[derive(Accounts)]
+pub struct FundRewardVault<'info> {
+    #[account(mut)]
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = treasurer @ StakingError::InvalidTreasurer,
+        has_one = reward_mint,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        associated_token::mint = reward_mint,
+        associated_token::authority = global_pool,
+        payer = treasurer,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = treasurer,
+    )]
+    pub treasurer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl FundRewardVault<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, amount: u64) -> Result<()> {
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.treasurer_token_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.treasurer.to_account_info(),
+        };
+        token::transfer(CpiContext::new(token_program, cpi_accounts), amount)?;
+
+        emit!(RewardVaultFunded {
+            treasurer: ctx.accounts.treasurer.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
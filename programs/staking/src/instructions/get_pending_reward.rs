@@ -0,0 +1,65 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct GetPendingReward<'info> {
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        seeds = [USER_POOL_SEED.as_ref(), user_pool.owner.as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+}
+
+/**
+ * Compute rewards claimable now without touching the accounts
+ * Per-NFT rewards are paged from offset, totals always cover every staked NFT
+ */
+pub fn get_pending_reward_handler(ctx: Context<GetPendingReward>, offset: u32) -> Result<PendingReward> {
+    // Settle on copies, so the view matches what claim_reward would pay
+    let mut global_pool = (**ctx.accounts.global_pool).clone();
+    let mut user_pool = (**ctx.accounts.user_pool).clone();
+    user_pool.items.truncate(user_pool.item_count as usize);
+    user_pool.settle_reward_epoch(&mut global_pool)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let reward_per_day = global_pool.reward_per_day;
+
+    let items = user_pool
+        .items
+        .iter()
+        .skip(offset as usize)
+        .take(MAX_REWARD_VIEW_ITEMS)
+        .map(|item| ItemReward {
+            nft_addr: item.nft_addr,
+            reward: user_pool.item_reward(item, now, reward_per_day),
+        })
+        .collect();
+
+    let creator_royalty = user_pool.accrued_royalty(now, reward_per_day);
+    let reward = user_pool
+        .accrued_reward(now, reward_per_day)
+        .checked_add(user_pool.pending_reward)
+        .unwrap();
+    let total = reward.checked_add(creator_royalty).unwrap();
+    let claim_fee = match global_pool.points_mode {
+        true => 0,
+        false => global_pool.claim_fee(reward),
+    };
+
+    Ok(PendingReward {
+        now,
+        item_count: user_pool.item_count,
+        pending_reward: user_pool.pending_reward,
+        legacy_reward: user_pool.legacy_reward,
+        total,
+        creator_royalty,
+        claim_fee,
+        net: reward - claim_fee,
+        items,
+    })
+}
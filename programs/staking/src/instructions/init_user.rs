@@ -5,6 +5,12 @@ pub struct InitUser<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
     //  User pool stores user's stake info
     #[account(
         init,
@@ -20,13 +26,23 @@ pub struct InitUser<'info> {
 }
 
 impl InitUser<'_> {
-    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+    pub fn process_instruction(ctx: &mut Context<Self>, referrer: Option<Pubkey>) -> Result<()> {
         let user = &mut ctx.accounts.user_pool;
 
+        if let Some(referrer) = referrer {
+            require!(!referrer.eq(&ctx.accounts.user.key()), StakingError::SelfReferral);
+        }
+
         let now = Clock::get()?.unix_timestamp;
 
+        user.version = UserPool::VERSION;
         user.owner = ctx.accounts.user.key();
         user.reward_time = now;
+        // Nothing accrued before any past reward mint migration
+        user.reward_epoch = ctx.accounts.global_pool.reward_epoch;
+        user.season = ctx.accounts.global_pool.points_season;
+        user.season_time = now;
+        user.referrer = referrer.unwrap_or_default();
 
         Ok(())
     }
@@ -1,4 +1,4 @@
-use crate::*;
+use {crate::*, anchor_spl::token::Mint};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -14,16 +14,38 @@ pub struct Initialize<'info> {
     )]
     pub global_pool: Account<'info, GlobalPool>,
 
+    pub reward_mint: Account<'info, Mint>,
+
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
 impl Initialize<'_> {
-    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+    pub fn process_instruction(ctx: &mut Context<Self>, max_reward_per_day: u64, points_mode: bool) -> Result<()> {
         let global_pool = &mut ctx.accounts.global_pool;
 
+        require!(
+            max_reward_per_day > 0 && max_reward_per_day <= REWARD_PER_DAY_CEILING,
+            StakingError::InvalidRewardCap
+        );
+
+        global_pool.version = GlobalPool::VERSION;
         global_pool.admin = ctx.accounts.admin.key();
 
+        // Reward cap, decimals and mode can't be changed later
+        global_pool.reward_mint = ctx.accounts.reward_mint.key();
+        global_pool.reward_decimals = ctx.accounts.reward_mint.decimals;
+        global_pool.max_reward_per_day = max_reward_per_day;
+        global_pool.points_mode = points_mode;
+
+        // All roles start with the admin, who can hand them over later
+        global_pool.operator = ctx.accounts.admin.key();
+        global_pool.pauser = ctx.accounts.admin.key();
+        global_pool.treasurer = ctx.accounts.admin.key();
+
+        global_pool.config_delay = DEFAULT_CONFIG_DELAY;
+        global_pool.liability_time = Clock::get()?.unix_timestamp;
+
         Ok(())
     }
 }
@@ -1,8 +1,14 @@
 use {
     crate::*, 
-    anchor_spl::token::{Mint, Token, TokenAccount},
+    anchor_lang::solana_program::sysvar::instructions as sysvar_instructions,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+        token_2022::Token2022,
+    },
     mpl_token_metadata::{
-        accounts::Metadata, instructions::{DelegateStakingV1CpiBuilder, LockV1CpiBuilder}, 
+        accounts::{MasterEdition, Metadata, TokenRecord},
+        instructions::{DelegateStakingV1CpiBuilder, LockV1CpiBuilder}, 
     }, 
 };
 use solana_program::pubkey::Pubkey;
@@ -17,14 +23,16 @@ pub struct LockPNFT<'info> {
         seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
         bump,
     )]
-    pub global_pool: Account<'info, GlobalPool>,
+    pub global_pool: Account<'info, Current<GlobalPool>>,
     
+    //  User pool is created on the first lock
+    /// CHECK: user pool PDA, created or loaded in the handler so a grown pool isn't checked against the initial size
     #[account(
         mut,
         seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
-        bump
+        bump,
     )]
-    pub user_pool: Account<'info, UserPool>,
+    pub user_pool: UncheckedAccount<'info>,
 
     pub token_mint: Box<Account<'info, Mint>>,
     #[account(
@@ -33,50 +41,122 @@ pub struct LockPNFT<'info> {
         token::authority = user,
     )]
     pub token_account: Box<Account<'info, TokenAccount>>,
-    /// CHECK: instruction will fail if wrong edition is supplied
+    /// CHECK: address is derived from token_mint
+    #[account(
+        address = MasterEdition::find_pda(&token_mint.key()).0 @ StakingError::InvalidEdition,
+    )]
     pub token_mint_edition: AccountInfo<'info>,
-    /// CHECK: instruction will fail if wrong record is supplied
-    #[account(mut)]
+    /// CHECK: address is derived from token_mint and token_account
+    #[account(
+        mut,
+        address = TokenRecord::find_pda(&token_mint.key(), &token_account.key()).0 @ StakingError::InvalidTokenRecord,
+    )]
     pub token_mint_record: AccountInfo<'info>,
-    /// CHECK: instruction will fail if wrong metadata is supplied
-    #[account(mut)]
+    /// CHECK: address is derived from token_mint and owned by token metadata program
+    #[account(
+        mut,
+        address = Metadata::find_pda(&token_mint.key()).0 @ StakingError::InvalidMetadata,
+        owner = mpl_token_metadata::ID @ StakingError::InvalidMetadataOwner,
+    )]
     pub mint_metadata: UncheckedAccount<'info>,
     /// CHECK: instruction will fail if wrong rules are supplied
     pub auth_rules: UncheckedAccount<'info>,
-    /// CHECK: instruction will fail if wrong sysvar ixns are supplied
+    /// CHECK: address is checked against sysvar instructions id
+    #[account(address = sysvar_instructions::ID @ StakingError::InvalidSysvarInstructions)]
     pub sysvar_instructions: AccountInfo<'info>,
 
     pub token_program: Program<'info, Token>,
-    /// CHECK: intstruction will fail if wrong program is supplied
+    /// CHECK: address is checked against token metadata program id
+    #[account(address = mpl_token_metadata::ID @ StakingError::InvalidTokenMetadataProgram)]
     pub token_metadata_program: AccountInfo<'info>,
-    /// CHECK: intstruction will fail if wrong program is supplied
+    /// CHECK: address is checked against auth rules program id
+    #[account(address = AUTH_RULES_PROGRAM_ID @ StakingError::InvalidAuthRulesProgram)]
     pub auth_rules_program: AccountInfo<'info>,
-    pub system_program: Program<'info, System>
+    pub system_program: Program<'info, System>,
+
+    //  Optional staking receipt, minted only if these accounts are passed
+    /// CHECK: receipt mint PDA of token_mint, created in the handler
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub receipt_mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: user's ATA of receipt_mint, checked and created in the handler
+    #[account(mut)]
+    pub receipt_account: Option<UncheckedAccount<'info>>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+    pub associated_token_program: Option<Program<'info, AssociatedToken>>,
+    //  Verified creators of the NFT, required only if creator share is set
+    #[account(
+        init_if_needed,
+        space = StakeCreators::DATA_SIZE,
+        seeds = [STAKE_CREATORS_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+        payer = user,
+    )]
+    pub stake_creators: Option<Box<Account<'info, StakeCreators>>>,
+
+    //  Receives the lock fee, required only if it's set
+    /// CHECK: address is checked against global_pool.treasury
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
 }
 
-pub fn lock_pnft_handler(ctx: Context<LockPNFT>) -> Result<()> {
+pub fn lock_pnft_handler<'info>(ctx: Context<'_, '_, '_, 'info, LockPNFT<'info>>) -> Result<()> {
     let global_pool = &mut ctx.accounts.global_pool;
-    let user_pool = &mut ctx.accounts.user_pool;
+    let user_pool_info = ctx.accounts.user_pool.to_account_info();
+
+    require!(!global_pool.emergency_mode, StakingError::EmergencyMode);
+    require!(!global_pool.is_paused(PAUSE_LOCK), StakingError::LockPaused);
+
+    // Create user pool on the first lock, an existing one is loaded whatever its size
+    let mut user_pool = if user_pool_info.owner.eq(&System::id()) {
+        create_pda_account(
+            ctx.accounts.user.as_ref(),
+            &user_pool_info,
+            UserPool::INIT_SIZE,
+            &[USER_POOL_SEED.as_bytes(), ctx.accounts.user.key().as_ref(), &[ctx.bumps.user_pool]],
+            ctx.accounts.system_program.as_ref(),
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        UserPool {
+            owner: ctx.accounts.user.key(),
+            reward_time: now,
+            reward_epoch: global_pool.reward_epoch,
+            season: global_pool.points_season,
+            season_time: now,
+            ..Default::default()
+        }
+    } else {
+        Current::<UserPool>::try_deserialize(&mut &user_pool_info.data.borrow()[..])?.into_inner()
+    };
 
     // Check user pool owner matched with signed user
     require!(user_pool.owner.eq(&ctx.accounts.user.key()), StakingError::InvalidOwner);
 
+    // Settle rewards accrued before the last reward mint migration
+    user_pool.settle_reward_epoch(global_pool)?;
+
     // Verify metadata is legit
-    let nft_metadata = Metadata::safe_deserialize(&mut ctx.accounts.mint_metadata.to_account_info().data.borrow_mut()).unwrap();
+    let nft_metadata = Metadata::safe_deserialize(&ctx.accounts.mint_metadata.to_account_info().data.borrow())
+        .map_err(|_| error!(StakingError::InvalidMetadata))?;
+    require!(nft_metadata.mint.eq(&ctx.accounts.token_mint.key()), StakingError::InvalidMetadata);
     
     // Check if this NFT is the wanted collection and verified
     let mut valid: u8 = 0;
-    if let Some(collection) = nft_metadata.collection {
+    if let Some(collection) = &nft_metadata.collection {
         msg!("collection: {}", collection.key.to_string());
-        if collection.key.to_string() == COLLECTION_ADDRESS {
+        if collection.verified && collection.key.to_string() == COLLECTION_ADDRESS {
             valid = 1;
         }
     } else {
         return Err(error!(StakingError::MetadataCreatorParseError));
     };
-    if let Some(creators) = nft_metadata.creators {
+    if let Some(creators) = &nft_metadata.creators {
         for creator in creators {
-            if creator.address.to_string() == COLLECTION_ADDRESS {
+            if creator.verified && creator.address.to_string() == COLLECTION_ADDRESS {
                 valid = 1;
                 break;
             }
@@ -128,9 +208,9 @@ pub fn lock_pnft_handler(ctx: Context<LockPNFT>) -> Result<()> {
         .invoke_signed(delegate_seeds)?;
 
     // Extend user pool PDA if capacity reached
-    if user_pool.items.capacity() == user_pool.item_count as usize {
+    if user_pool.is_full(user_pool_info.data_len()) {
         resize_account(
-            user_pool.to_account_info().clone(),
+            user_pool_info.clone(),
             UserPool::size_calc(user_pool.item_count + DEFAULT_STAKE_SIZE as u64),
             ctx.accounts.user.to_account_info().clone(),
             ctx.accounts.system_program.to_account_info().clone()
@@ -138,17 +218,113 @@ pub fn lock_pnft_handler(ctx: Context<LockPNFT>) -> Result<()> {
     }
 
     // Update user staking info
+    // no season checkpoint is needed, the new item only counts toward seasons from its stake time
     let timestamp = Clock::get()?.unix_timestamp;
 
+    // Capture verified creators, so claims don't need the metadata
+    let mut creator_share_bps = 0;
+    if global_pool.creator_share_bps > 0 {
+        let stake_creators = ctx
+            .accounts
+            .stake_creators
+            .as_mut()
+            .ok_or(error!(StakingError::InvalidStakeCreators))?;
+
+        // Creators with unpaid royalty of an earlier stake are kept
+        if stake_creators.version == 0 {
+            stake_creators.version = StakeCreators::VERSION;
+            stake_creators.mint = ctx.accounts.token_mint.key();
+            stake_creators.payer = ctx.accounts.user.key();
+        }
+        if stake_creators.unpaid() > 0 {
+            stake_creators.settle_reward_epoch(global_pool)?;
+        } else {
+            stake_creators.reward_epoch = global_pool.reward_epoch;
+            stake_creators.creators = nft_metadata
+                .creators
+                .iter()
+                .flatten()
+                .filter(|creator| creator.verified)
+                .map(|creator| CreatorRoyalty {
+                    address: creator.address,
+                    share: creator.share,
+                    unpaid: 0,
+                    legacy: 0,
+                })
+                .collect();
+        }
+        // NFTs without verified creators keep their whole reward
+        if stake_creators.creators.iter().any(|creator| creator.share > 0) {
+            creator_share_bps = global_pool.creator_share_bps;
+        }
+        stake_creators.creator_share_bps = creator_share_bps;
+        stake_creators.royalty_time = timestamp;
+        stake_creators.staked = true;
+    }
+
     let staked_item = StakedNFT {
         nft_addr: ctx.accounts.token_mint.key(),
         stake_time: timestamp,
-        extra: 0,
+        creator_share_bps,
+        ..Default::default()
     };
 
-    user_pool.add_nft(staked_item);
+    user_pool.add_nft(staked_item)?;
+    user_pool.try_serialize(&mut &mut user_pool_info.data.borrow_mut()[..])?;
+    global_pool.accrue_liability(timestamp);
     global_pool.total_staked_count += 1;
 
+    // Mint wallet-visible staking receipt if requested
+    if let Some(receipt_mint) = &ctx.accounts.receipt_mint {
+        let (Some(receipt_account), Some(token_2022_program), Some(associated_token_program)) = (
+            &ctx.accounts.receipt_account,
+            &ctx.accounts.token_2022_program,
+            &ctx.accounts.associated_token_program,
+        ) else {
+            return err!(StakingError::InvalidReceiptAccounts);
+        };
+        ReceiptAccounts {
+            global_pool: ctx.accounts.global_pool.to_account_info(),
+            global_bump: ctx.bumps.global_pool,
+            receipt_mint: receipt_mint.to_account_info(),
+            receipt_bump: ctx.bumps.receipt_mint,
+            receipt_account: receipt_account.to_account_info(),
+            owner: ctx.accounts.user.to_account_info(),
+            token_2022_program: token_2022_program.to_account_info(),
+        }
+        .mint_receipt(
+            &nft_metadata,
+            associated_token_program.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
+    let lock_fee = collect_lock_fee(
+        &ctx.accounts.global_pool,
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.treasury.as_ref().map(|treasury| treasury.to_account_info()),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    if lock_fee > 0 {
+        emit!(LockFeeCollected {
+            user: ctx.accounts.user.key(),
+            mint: ctx.accounts.token_mint.key(),
+            action: HookAction::Lock,
+            amount: lock_fee,
+        });
+    }
+
+    invoke_stake_hook(
+        &ctx.accounts.global_pool,
+        ctx.remaining_accounts,
+        StakeHookArgs {
+            owner: ctx.accounts.user.key(),
+            mint: ctx.accounts.token_mint.key(),
+            action: HookAction::Lock,
+            timestamp,
+        },
+    )?;
+
     Ok(())
 
 }
\ No newline at end of file
@@ -0,0 +1,111 @@
+use {crate::*, anchor_lang::Discriminator, anchor_spl::token::Mint};
+
+#[derive(Accounts)]
+pub struct MigrateGlobalPool<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: legacy layout can't be loaded as GlobalPool, checked in the handler
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub global_pool: UncheckedAccount<'info>,
+
+    pub reward_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl MigrateGlobalPool<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, max_reward_per_day: u64) -> Result<()> {
+        let global_info = ctx.accounts.global_pool.to_account_info();
+        let admin = ctx.accounts.admin.key();
+
+        let data = global_info.try_borrow_data()?;
+        require!(
+            data.len() > 41 && data[..8] == GlobalPool::DISCRIMINATOR,
+            StakingError::InvalidAccountVersion
+        );
+
+        // Unversioned layout starts with the admin key right after the discriminator
+        let global_pool = if data[8..40] == admin.to_bytes() {
+            let legacy = GlobalPoolV0::deserialize(&mut &data[8..])?;
+
+            require!(
+                max_reward_per_day > 0 && max_reward_per_day <= REWARD_PER_DAY_CEILING,
+                StakingError::InvalidRewardCap
+            );
+            require!(legacy.reward_per_day <= max_reward_per_day, StakingError::RewardPerDayTooHigh);
+            require!(
+                ctx.accounts.reward_mint.key().eq(&legacy.reward_mint),
+                StakingError::InvalidRewardMint
+            );
+
+            GlobalPool {
+                version: GlobalPool::VERSION,
+                admin,
+                reward_per_day: legacy.reward_per_day,
+                reward_mint: legacy.reward_mint,
+                reward_enable: legacy.reward_enable,
+                total_staked_count: legacy.total_staked_count,
+                operator: admin,
+                pauser: admin,
+                treasurer: admin,
+                config_delay: DEFAULT_CONFIG_DELAY,
+                liability_time: Clock::get()?.unix_timestamp,
+                reward_decimals: ctx.accounts.reward_mint.decimals,
+                max_reward_per_day,
+                extra: legacy.extra,
+                ..Default::default()
+            }
+        } else {
+            require!(data[8] == 1, StakingError::AlreadyMigrated);
+            require!(data[9..41] == admin.to_bytes(), StakingError::InvalidAdmin);
+            let legacy = GlobalPoolV1::deserialize(&mut &data[8..])?;
+
+            // Version 1 pools keep their reward cap, fields added since start disabled
+            GlobalPool {
+                version: GlobalPool::VERSION,
+                admin,
+                reward_per_day: legacy.reward_per_day,
+                reward_mint: legacy.reward_mint,
+                reward_enable: legacy.reward_enable,
+                total_staked_count: legacy.total_staked_count,
+                pending_admin: legacy.pending_admin,
+                operator: legacy.operator,
+                pauser: legacy.pauser,
+                treasurer: legacy.treasurer,
+                pause_flags: legacy.pause_flags,
+                config_delay: legacy.config_delay,
+                emergency_mode: legacy.emergency_mode,
+                reward_epoch: legacy.reward_epoch,
+                reward_liability: legacy.reward_liability,
+                liability_time: legacy.liability_time,
+                migration: legacy.migration,
+                reward_decimals: legacy.reward_decimals,
+                max_reward_per_day: legacy.max_reward_per_day,
+                extra: legacy.extra,
+                ..Default::default()
+            }
+        };
+        drop(data);
+
+        resize_account(
+            global_info.clone(),
+            GlobalPool::DATA_SIZE,
+            ctx.accounts.admin.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        )?;
+        global_pool.try_serialize(&mut &mut global_info.try_borrow_mut_data()?[..])?;
+
+        emit!(AccountMigrated {
+            account: global_info.key(),
+            version: GlobalPool::VERSION,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use {crate::*, anchor_lang::Discriminator};
+
+#[derive(Accounts)]
+pub struct MigrateUserPool<'info> {
+    //  Anyone can migrate a user pool, payer covers the extra rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: owner of the user pool
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: legacy layout can't be loaded as UserPool, checked in the handler
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), owner.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub user_pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_user_pool_handler(ctx: Context<MigrateUserPool>) -> Result<()> {
+    let user_info = ctx.accounts.user_pool.to_account_info();
+    let owner = ctx.accounts.owner.key();
+
+    let data = user_info.try_borrow_data()?;
+    require!(
+        data.len() > 41 && data[..8] == UserPool::DISCRIMINATOR,
+        StakingError::InvalidAccountVersion
+    );
+
+    // Both layouts predate seasons, so the pool settles every ended season from season 0
+    // Unversioned layout starts with the owner key right after the discriminator
+    let user_pool = if data[8..40] == owner.to_bytes() {
+        let legacy = UserPoolV0::deserialize(&mut &data[8..])?;
+
+        let mut items = legacy.items;
+        // Drop stale entries left past item_count by older versions
+        items.truncate(legacy.item_count as usize);
+
+        // Unversioned pools predate reward mint migrations, so they settle from epoch 0
+        UserPool {
+            version: UserPool::VERSION,
+            owner,
+            item_count: items.len() as u64,
+            reward_time: legacy.reward_time,
+            pending_reward: legacy.pending_reward,
+            reward_epoch: 0,
+            legacy_reward: 0,
+            extra: legacy.extra,
+            items,
+            ..Default::default()
+        }
+    } else {
+        require!(data[8] == 1, StakingError::AlreadyMigrated);
+        require!(data[9..41] == owner.to_bytes(), StakingError::InvalidOwner);
+        let legacy = UserPoolV1::deserialize(&mut &data[8..])?;
+
+        UserPool {
+            version: UserPool::VERSION,
+            owner,
+            item_count: legacy.item_count,
+            reward_time: legacy.reward_time,
+            pending_reward: legacy.pending_reward,
+            reward_epoch: legacy.reward_epoch,
+            legacy_reward: legacy.legacy_reward,
+            extra: legacy.extra,
+            items: legacy.items,
+            ..Default::default()
+        }
+    };
+    drop(data);
+
+    // Newer layouts only add fields, only grow so rent is never refunded here
+    let new_space = user_info
+        .data_len()
+        .max(UserPool::size_calc(user_pool.item_count + DEFAULT_STAKE_SIZE as u64));
+    resize_account(
+        user_info.clone(),
+        new_space,
+        ctx.accounts.payer.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    user_pool.try_serialize(&mut &mut user_info.try_borrow_mut_data()?[..])?;
+
+    emit!(AccountMigrated {
+        account: user_info.key(),
+        version: UserPool::VERSION,
+    });
+
+    Ok(())
+}
@@ -8,5 +8,81 @@ pub mod claim_reward;
 pub use claim_reward::*;
 pub mod unlock_pnft;
 pub use unlock_pnft::*;
-pub mod change_reward_env;
-pub use change_reward_env::*;
+pub mod queue_config_change;
+pub use queue_config_change::*;
+pub mod execute_config_change;
+pub use execute_config_change::*;
+pub mod cancel_config_change;
+pub use cancel_config_change::*;
+pub mod reconcile_user_pool;
+pub use reconcile_user_pool::*;
+pub mod close_user_pool;
+pub use close_user_pool::*;
+pub mod propose_admin;
+pub use propose_admin::*;
+pub mod accept_admin;
+pub use accept_admin::*;
+pub mod cancel_admin_transfer;
+pub use cancel_admin_transfer::*;
+pub mod set_role;
+pub use set_role::*;
+pub mod fund_reward_vault;
+pub use fund_reward_vault::*;
+pub mod withdraw_reward_vault;
+pub use withdraw_reward_vault::*;
+pub mod pause;
+pub use pause::*;
+pub mod unpause;
+pub use unpause::*;
+pub mod set_emergency_mode;
+pub use set_emergency_mode::*;
+pub mod emergency_unlock;
+pub use emergency_unlock::*;
+pub mod start_reward_mint_migration;
+pub use start_reward_mint_migration::*;
+pub mod finalize_reward_mint_migration;
+pub use finalize_reward_mint_migration::*;
+pub mod settle_reward_epoch;
+pub use settle_reward_epoch::*;
+pub mod claim_legacy_reward;
+pub use claim_legacy_reward::*;
+pub mod force_unlock;
+pub use force_unlock::*;
+pub mod migrate_global_pool;
+pub use migrate_global_pool::*;
+pub mod migrate_user_pool;
+pub use migrate_user_pool::*;
+pub mod get_pending_reward;
+pub use get_pending_reward::*;
+pub mod assert_staked;
+pub use assert_staked::*;
+pub mod staked_count;
+pub use staked_count::*;
+pub mod set_hook_program;
+pub use set_hook_program::*;
+pub mod create_voter_weight_record;
+pub use create_voter_weight_record::*;
+pub mod update_voter_weight_record;
+pub use update_voter_weight_record::*;
+pub mod spend_points;
+pub use spend_points::*;
+pub mod start_season;
+pub use start_season::*;
+pub mod end_season;
+pub use end_season::*;
+pub mod set_referral_config;
+pub use set_referral_config::*;
+pub mod claim_creator_royalty;
+pub use claim_creator_royalty::*;
+pub mod queue_reward_mint_migration;
+pub use queue_reward_mint_migration::*;
+pub mod settle_user_season;
+pub use settle_user_season::*;
+pub mod cast_nft_vote;
+pub use cast_nft_vote::*;
+pub mod relinquish_nft_vote;
+pub use relinquish_nft_vote::*;
+pub mod withdraw_legacy_vault;
+pub use withdraw_legacy_vault::*;
+pub mod settle_royalty_epoch;
+pub use settle_royalty_epoch::*;
@@ -0,0 +1,40 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    #[account(mut)]
+    pub pauser: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = pauser @ StakingError::InvalidPauser,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl Pause<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, flags: u8) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        require!(flags != 0 && flags & !PAUSE_ALL == 0, StakingError::InvalidPauseFlags);
+        // Unlocks stay open unless emergency_unlock is available instead
+        require!(
+            flags & PAUSE_UNLOCK == 0 || global_pool.emergency_mode,
+            StakingError::UnlockPauseNeedsEmergencyMode
+        );
+
+        // Pauser can only add flags, unpausing is up to the admin
+        let old_flags = global_pool.pause_flags;
+        global_pool.pause_flags |= flags;
+
+        emit!(PauseUpdated {
+            authority: ctx.accounts.pauser.key(),
+            old_flags,
+            new_flags: global_pool.pause_flags,
+        });
+
+        Ok(())
+    }
+}
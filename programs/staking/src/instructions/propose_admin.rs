@@ -0,0 +1,33 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = admin @ StakingError::InvalidAdmin,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl ProposeAdmin<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, new_admin: Pubkey) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        require!(!new_admin.eq(&Pubkey::default()), StakingError::InvalidPendingAdmin);
+
+        // New admin takes effect only after accept_admin is signed by it
+        global_pool.pending_admin = new_admin;
+
+        emit!(AdminTransferProposed {
+            admin: global_pool.admin,
+            pending_admin: new_admin,
+        });
+
+        Ok(())
+    }
+}
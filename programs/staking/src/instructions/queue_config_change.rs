@@ -0,0 +1,108 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct QueueConfigChange<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = operator @ StakingError::InvalidOperator,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    //  Only one config change can be queued at a time
+    #[account(
+        init,
+        space = ConfigChange::DATA_SIZE,
+        seeds = [CONFIG_CHANGE_SEED.as_ref()],
+        bump,
+        payer = operator
+    )]
+    pub config_change: Account<'info, ConfigChange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl QueueConfigChange<'_> {
+    pub fn process_instruction(
+        ctx: &mut Context<Self>,
+        new_reward_enable: Option<bool>,
+        new_reward_per_day: Option<u64>,
+        new_config_delay: Option<i64>,
+        new_fee_config: Option<FeeConfig>,
+        new_creator_share_bps: Option<u16>,
+    ) -> Result<()> {
+        require!(
+            new_reward_enable.is_some()
+                || new_reward_per_day.is_some()
+                || new_config_delay.is_some()
+                || new_fee_config.is_some()
+                || new_creator_share_bps.is_some(),
+            StakingError::EmptyConfigChange
+        );
+        if let Some(delay) = new_config_delay {
+            require!(
+                (MIN_CONFIG_DELAY..=MAX_CONFIG_DELAY).contains(&delay),
+                StakingError::InvalidConfigDelay
+            );
+        }
+        if let Some(fee_config) = new_fee_config {
+            require!(fee_config.is_valid(), StakingError::InvalidFeeConfig);
+        }
+
+        // Validate the resulting reward config
+        let global_pool = &ctx.accounts.global_pool;
+        if let Some(creator_share_bps) = new_creator_share_bps {
+            // Royalties are paid in reward token, points aren't shared
+            require!(!global_pool.points_mode, StakingError::PointsMode);
+            require!(creator_share_bps <= MAX_CREATOR_SHARE_BPS, StakingError::InvalidCreatorShare);
+        }
+        let reward_enable = new_reward_enable.unwrap_or(global_pool.reward_enable);
+        let reward_per_day = new_reward_per_day.unwrap_or(global_pool.reward_per_day);
+        let config_delay = new_config_delay.unwrap_or(global_pool.config_delay);
+        require!(
+            reward_per_day <= global_pool.max_reward_per_day,
+            StakingError::RewardPerDayTooHigh
+        );
+        require!(
+            !reward_enable || reward_per_day > 0,
+            StakingError::ZeroRewardEnabled
+        );
+        require!(
+            reward_enable != global_pool.reward_enable
+                || reward_per_day != global_pool.reward_per_day
+                || config_delay != global_pool.config_delay
+                || new_fee_config.is_some_and(|fee_config| fee_config != global_pool.fee_config())
+                || new_creator_share_bps.is_some_and(|share| share != global_pool.creator_share_bps),
+            StakingError::NoOpConfigChange
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let config_change = &mut ctx.accounts.config_change;
+
+        config_change.version = ConfigChange::VERSION;
+        config_change.proposer = ctx.accounts.operator.key();
+        config_change.new_reward_enable = new_reward_enable;
+        config_change.new_reward_per_day = new_reward_per_day;
+        config_change.new_config_delay = new_config_delay;
+        config_change.new_fee_config = new_fee_config;
+        config_change.new_creator_share_bps = new_creator_share_bps;
+        config_change.queued_at = now;
+        // A delay change waits out the longer of both delays, a shorter one only applies to later changes
+        config_change.eta = now + ctx.accounts.global_pool.config_delay.max(config_delay);
+
+        emit!(ConfigChangeQueued {
+            proposer: config_change.proposer,
+            new_reward_enable,
+            new_reward_per_day,
+            new_config_delay,
+            new_fee_config,
+            new_creator_share_bps,
+            eta: config_change.eta,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use {crate::*, anchor_spl::token::Mint};
+
+#[derive(Accounts)]
+pub struct QueueRewardMintMigration<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = operator @ StakingError::InvalidOperator,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    //  Shares the config change slot, so it can't be queued along with another change
+    #[account(
+        init,
+        space = ConfigChange::DATA_SIZE,
+        seeds = [CONFIG_CHANGE_SEED.as_ref()],
+        bump,
+        payer = operator
+    )]
+    pub config_change: Account<'info, ConfigChange>,
+
+    pub new_reward_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl QueueRewardMintMigration<'_> {
+    pub fn process_instruction(
+        ctx: &mut Context<Self>,
+        claim_window: i64,
+        ratio_num: u64,
+        ratio_den: u64,
+    ) -> Result<()> {
+        let global_pool = &ctx.accounts.global_pool;
+        let new_mint = ctx.accounts.new_reward_mint.key();
+
+        require!(!global_pool.points_mode, StakingError::PointsMode);
+        require!(!global_pool.migration.active, StakingError::MigrationInProgress);
+        require!(!new_mint.eq(&global_pool.reward_mint), StakingError::SameRewardMint);
+        require!(
+            ctx.accounts.new_reward_mint.decimals == global_pool.reward_decimals,
+            StakingError::RewardMintDecimalsMismatch
+        );
+
+        let migration = MigrationConfig {
+            new_mint,
+            claim_window,
+            ratio_num,
+            ratio_den,
+        };
+        require!(migration.is_valid(), StakingError::InvalidMigrationConfig);
+
+        let now = Clock::get()?.unix_timestamp;
+        let config_change = &mut ctx.accounts.config_change;
+
+        config_change.version = ConfigChange::VERSION;
+        config_change.proposer = ctx.accounts.operator.key();
+        config_change.new_migration = Some(migration);
+        config_change.queued_at = now;
+        config_change.eta = now + global_pool.config_delay;
+
+        emit!(RewardMintMigrationQueued {
+            proposer: config_change.proposer,
+            new_mint,
+            claim_window,
+            ratio_num,
+            ratio_den,
+            eta: config_change.eta,
+        });
+
+        Ok(())
+    }
+}
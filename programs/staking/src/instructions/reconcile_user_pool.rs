@@ -0,0 +1,107 @@
+use {
+    crate::*,
+    anchor_spl::token::{Token, TokenAccount},
+    mpl_token_metadata::{
+        accounts::TokenRecord,
+        types::{TokenDelegateRole, TokenState},
+    },
+};
+
+#[derive(Accounts)]
+pub struct ReconcileUserPool<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+    // remaining_accounts: [token_account, token_mint_record] for each entry of user_pool.items
+}
+
+/**
+ * Drop user pool entries whose pNFT is no longer locked to the global authority
+ * and fix item_count / total_staked_count accordingly
+ * token_account must be the one holding the NFT now, its token record confirms the NFT was unlocked
+ */
+pub fn reconcile_user_pool_handler(ctx: Context<ReconcileUserPool>) -> Result<()> {
+    let global_pool = &mut ctx.accounts.global_pool;
+    let user_pool = &mut ctx.accounts.user_pool;
+
+    require!(!global_pool.is_paused(PAUSE_UNLOCK), StakingError::UnlockPaused);
+
+    // Check user pool owner matched with signed user
+    require!(user_pool.owner.eq(&ctx.accounts.user.key()), StakingError::InvalidOwner);
+
+    let remaining_accounts = ctx.remaining_accounts;
+    require!(
+        remaining_accounts.len() == user_pool.items.len() * 2,
+        StakingError::InvalidRemainingAccounts
+    );
+
+    let old_count = user_pool.item_count;
+    let mut items: Vec<StakedNFT> = Vec::with_capacity(user_pool.items.len());
+
+    for (index, item) in user_pool.items.iter().enumerate() {
+        let token_account_info = &remaining_accounts[index * 2];
+        let token_record_info = &remaining_accounts[index * 2 + 1];
+
+        require!(token_account_info.owner.eq(&Token::id()), StakingError::InvalidTokenAccount);
+        let token_account = TokenAccount::try_deserialize(&mut &token_account_info.data.borrow()[..])
+            .map_err(|_| error!(StakingError::InvalidTokenAccount))?;
+        require!(token_account.mint.eq(&item.nft_addr), StakingError::InvalidTokenAccount);
+        require!(
+            token_record_info
+                .key()
+                .eq(&TokenRecord::find_pda(&item.nft_addr, &token_account_info.key()).0),
+            StakingError::InvalidTokenRecord
+        );
+
+        // Duplicated entries are always dropped
+        if items.iter().any(|staked| staked.nft_addr.eq(&item.nft_addr)) {
+            continue;
+        }
+
+        // An entry is only dropped once the token account holding the NFT shows it isn't locked to this
+        // user's stake anymore, entries that can't be verified are kept
+        let record = match token_record_info.owner.eq(&mpl_token_metadata::ID) {
+            true => TokenRecord::safe_deserialize(&token_record_info.data.borrow()).ok(),
+            false => None,
+        };
+        let stale = match record {
+            Some(record) if token_account.amount == 1 => {
+                !(token_account.owner.eq(&user_pool.owner)
+                    && record.state == TokenState::Locked
+                    && record.delegate == Some(global_pool.key())
+                    && record.delegate_role == Some(TokenDelegateRole::Staking))
+            }
+            _ => false,
+        };
+
+        if stale {
+            msg!("Drop stale entry: {}", item.nft_addr);
+        } else {
+            items.push(item.clone());
+        }
+    }
+
+    user_pool.items = items;
+    user_pool.item_count = user_pool.items.len() as u64;
+
+    global_pool.accrue_liability(Clock::get()?.unix_timestamp);
+    global_pool.total_staked_count = global_pool
+        .total_staked_count
+        .saturating_sub(old_count)
+        .saturating_add(user_pool.item_count);
+
+    Ok(())
+}
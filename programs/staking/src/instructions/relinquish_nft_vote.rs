@@ -0,0 +1,50 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct RelinquishNftVote<'info> {
+    #[account(mut)]
+    pub governing_token_owner: Signer<'info>,
+
+    #[account(
+        has_one = governing_token_owner @ StakingError::InvalidOwner,
+        has_one = realm @ StakingError::InvalidProposal,
+    )]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    /// CHECK: realm of the voter weight record, owned by the governance program
+    pub realm: UncheckedAccount<'info>,
+
+    /// CHECK: owner and state are checked in the handler
+    pub proposal: UncheckedAccount<'info>,
+}
+
+impl<'info> RelinquishNftVote<'info> {
+    /**
+     * remaining_accounts are the owner's NFT vote records of the proposal to close
+     * Records are kept while the proposal is voting, so NFTs can't vote on it twice
+     */
+    pub fn process_instruction(ctx: &mut Context<'_, '_, '_, 'info, Self>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let owner = ctx.accounts.governing_token_owner.to_account_info();
+
+        // A proposal account that no longer exists can't be voting
+        if !proposal.data_is_empty() {
+            require!(proposal.owner.eq(ctx.accounts.realm.owner), StakingError::InvalidProposal);
+            let data = proposal.try_borrow_data()?;
+            require!(data.len() > PROPOSAL_STATE_OFFSET, StakingError::InvalidProposal);
+            require!(data[PROPOSAL_STATE_OFFSET] != PROPOSAL_STATE_VOTING, StakingError::ProposalVoting);
+        }
+
+        for record_info in ctx.remaining_accounts {
+            require!(record_info.owner.eq(&crate::ID), StakingError::InvalidNftVoteRecord);
+            let record = Current::<NftVoteRecord>::try_deserialize(&mut &record_info.data.borrow()[..])?;
+            require!(
+                record.proposal.eq(&proposal.key()) && record.governing_token_owner.eq(&owner.key()),
+                StakingError::InvalidNftVoteRecord
+            );
+            close_account(record_info, &owner)?;
+        }
+
+        Ok(())
+    }
+}
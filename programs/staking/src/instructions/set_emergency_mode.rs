@@ -0,0 +1,42 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetEmergencyMode<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = admin @ StakingError::InvalidAdmin,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl SetEmergencyMode<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, enabled: bool) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        global_pool.emergency_mode = enabled;
+
+        emit!(EmergencyModeUpdated {
+            admin: global_pool.admin,
+            enabled,
+        });
+
+        // Unlock pause is only allowed while emergency_unlock is available
+        if !enabled && global_pool.is_paused(PAUSE_UNLOCK) {
+            let old_flags = global_pool.pause_flags;
+            global_pool.pause_flags &= !PAUSE_UNLOCK;
+
+            emit!(PauseUpdated {
+                authority: global_pool.admin,
+                old_flags,
+                new_flags: global_pool.pause_flags,
+            });
+        }
+
+        Ok(())
+    }
+}
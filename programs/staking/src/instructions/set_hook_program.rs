@@ -0,0 +1,37 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetHookProgram<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = admin @ StakingError::InvalidAdmin,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl SetHookProgram<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, hook_program: Option<Pubkey>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        if let Some(program) = hook_program {
+            require!(
+                !program.eq(&Pubkey::default()) && !program.eq(&crate::ID),
+                StakingError::InvalidHookProgram
+            );
+        }
+
+        global_pool.hook_program = hook_program.unwrap_or_default();
+
+        emit!(HookProgramUpdated {
+            admin: global_pool.admin,
+            hook_program,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,34 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetReferralConfig<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = operator @ StakingError::InvalidOperator,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl SetReferralConfig<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, referral_bps: u16, referral_cap: u64) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        require!(referral_bps <= MAX_REFERRAL_BPS, StakingError::InvalidReferralConfig);
+
+        global_pool.referral_bps = referral_bps;
+        global_pool.referral_cap = referral_cap;
+
+        emit!(ReferralConfigUpdated {
+            operator: global_pool.operator,
+            referral_bps,
+            referral_cap,
+        });
+
+        Ok(())
+    }
+}
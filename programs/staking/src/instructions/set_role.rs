@@ -0,0 +1,32 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = admin @ StakingError::InvalidAdmin,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl SetRole<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, role: Role, new_key: Pubkey) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        let old_key = global_pool.role(role);
+        global_pool.set_role(role, new_key);
+
+        emit!(RoleUpdated {
+            role,
+            old_key,
+            new_key,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SettleRewardEpoch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user_pool.owner.as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+}
+
+impl<'info> SettleRewardEpoch<'info> {
+    /**
+     * remaining_accounts are the migration archives of the reward epochs the user skipped, in order,
+     * starting from user_pool.reward_epoch. None are needed if the user is at most one epoch behind
+     * without legacy reward left
+     */
+    pub fn process_instruction(ctx: &mut Context<'_, '_, '_, 'info, Self>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+        let user_pool = &mut ctx.accounts.user_pool;
+
+        require!(!global_pool.is_paused(PAUSE_CLAIM), StakingError::ClaimPaused);
+
+        for account in ctx.remaining_accounts {
+            if user_pool.reward_epoch >= global_pool.reward_epoch {
+                break;
+            }
+            let archive = load_migration_archive(account, user_pool.reward_epoch)?;
+            user_pool.settle_archived_epoch(&archive, global_pool.reward_per_day)?;
+        }
+        user_pool.settle_reward_epoch(global_pool)?;
+
+        Ok(())
+    }
+}
+
+pub(crate) fn load_migration_archive(account: &AccountInfo, epoch: u16) -> Result<MigrationArchive> {
+    let (address, _) = Pubkey::find_program_address(
+        &[MIGRATION_ARCHIVE_SEED.as_bytes(), epoch.to_le_bytes().as_ref()],
+        &crate::ID,
+    );
+    require!(
+        account.key().eq(&address) && account.owner.eq(&crate::ID),
+        StakingError::InvalidMigrationArchive
+    );
+    MigrationArchive::try_deserialize(&mut &account.data.borrow()[..])
+}
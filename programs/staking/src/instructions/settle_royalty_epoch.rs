@@ -0,0 +1,45 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SettleRoyaltyEpoch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_CREATORS_SEED.as_ref(), stake_creators.mint.as_ref()],
+        bump,
+    )]
+    pub stake_creators: Account<'info, Current<StakeCreators>>,
+}
+
+impl<'info> SettleRoyaltyEpoch<'info> {
+    /**
+     * remaining_accounts are the migration archives of the reward epochs the stake creators skipped, in order,
+     * starting from stake_creators.reward_epoch, as in settle_reward_epoch
+     */
+    pub fn process_instruction(ctx: &mut Context<'_, '_, '_, 'info, Self>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+        let stake_creators = &mut ctx.accounts.stake_creators;
+
+        require!(!global_pool.is_paused(PAUSE_CLAIM), StakingError::ClaimPaused);
+
+        for account in ctx.remaining_accounts {
+            if stake_creators.reward_epoch >= global_pool.reward_epoch {
+                break;
+            }
+            let archive = load_migration_archive(account, stake_creators.reward_epoch)?;
+            stake_creators.settle_archived_epoch(&archive, global_pool.reward_per_day)?;
+        }
+        stake_creators.settle_reward_epoch(global_pool)?;
+
+        Ok(())
+    }
+}
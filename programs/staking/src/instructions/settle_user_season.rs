@@ -0,0 +1,74 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SettleUserSeason<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user_pool.owner.as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+
+    #[account(
+        mut,
+        seeds = [SEASON_SEED.as_ref(), user_pool.season.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub season: Account<'info, Current<Season>>,
+
+    #[account(
+        init_if_needed,
+        space = UserSeason::DATA_SIZE,
+        seeds = [USER_SEASON_SEED.as_ref(), user_pool.season.to_le_bytes().as_ref(), user_pool.owner.as_ref()],
+        bump,
+        payer = payer,
+    )]
+    pub user_season: Account<'info, UserSeason>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl SettleUserSeason<'_> {
+    /**
+     * Count the reward accrued since the user's last checkpoint until the end of an ended season
+     * Call once per season the user is behind, in order
+     */
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let global_pool = &ctx.accounts.global_pool;
+        let user_pool = &mut ctx.accounts.user_pool;
+        let season = &mut ctx.accounts.season;
+        let user_season = &mut ctx.accounts.user_season;
+
+        require!(
+            user_pool.season < global_pool.points_season && !season.active,
+            StakingError::SeasonActive
+        );
+
+        let from = season.start_time.max(user_pool.season_time);
+        let season_reward = user_pool.season_reward(from, season.end_time, season.reward_per_day);
+        if season_reward > 0 {
+            if user_season.version == 0 {
+                user_season.version = UserSeason::VERSION;
+                user_season.season = season.id;
+                user_season.owner = user_pool.owner;
+                season.participants += 1;
+            }
+            user_season.reward = user_season.reward.checked_add(season_reward).unwrap();
+            season.total_reward = season.total_reward.checked_add(season_reward).unwrap();
+        }
+
+        user_pool.season += 1;
+        user_pool.season_time = season.end_time;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SpendPoints<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = operator @ StakingError::InvalidOperator,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [USER_POOL_SEED.as_ref(), user_pool.owner.as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+}
+
+impl SpendPoints<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, amount: u64, redemption_id: u64) -> Result<()> {
+        let global_pool = &ctx.accounts.global_pool;
+        let user_pool = &mut ctx.accounts.user_pool;
+
+        require!(!global_pool.is_paused(PAUSE_CLAIM), StakingError::ClaimPaused);
+        require!(global_pool.points_mode, StakingError::NotPointsMode);
+        require!(user_pool.available_points() >= amount, StakingError::InsufficientPoints);
+
+        user_pool.points_spent = user_pool.points_spent.checked_add(amount).unwrap();
+
+        emit!(PointsSpent {
+            operator: global_pool.operator,
+            user: user_pool.owner,
+            amount,
+            available: user_pool.available_points(),
+            redemption_id,
+        });
+
+        Ok(())
+    }
+}
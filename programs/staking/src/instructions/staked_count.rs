@@ -0,0 +1,27 @@
+use crate::*;
+
+#[derive(Accounts)]
+#[instruction(owner: Pubkey)]
+pub struct StakedCount<'info> {
+    /// CHECK: may not be initialized yet, which counts as nothing staked
+    #[account(
+        seeds = [USER_POOL_SEED.as_ref(), owner.as_ref()],
+        bump,
+    )]
+    pub user_pool: UncheckedAccount<'info>,
+}
+
+/**
+ * Number of NFTs the owner has staked, 0 if the user pool doesn't exist
+ */
+pub fn staked_count_handler(ctx: Context<StakedCount>, _owner: Pubkey) -> Result<u64> {
+    let user_pool_info = ctx.accounts.user_pool.to_account_info();
+    if user_pool_info.data_is_empty() {
+        return Ok(0);
+    }
+
+    require!(user_pool_info.owner.eq(&crate::ID), StakingError::InvalidOwner);
+    let user_pool = Current::<UserPool>::try_deserialize(&mut &user_pool_info.data.borrow()[..])?;
+
+    Ok(user_pool.item_count)
+}
@@ -0,0 +1,97 @@
+use {crate::*, anchor_spl::token::Mint};
+
+#[derive(Accounts)]
+pub struct StartRewardMintMigration<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_CHANGE_SEED.as_ref()],
+        bump,
+        has_one = proposer,
+        close = proposer,
+    )]
+    pub config_change: Account<'info, Current<ConfigChange>>,
+
+    /// CHECK: rent of the config change is refunded to its proposer
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    //  Migration of the ending reward epoch, kept to settle users who skip the new one
+    #[account(
+        init,
+        space = MigrationArchive::DATA_SIZE,
+        seeds = [MIGRATION_ARCHIVE_SEED.as_ref(), global_pool.reward_epoch.to_le_bytes().as_ref()],
+        bump,
+        payer = payer
+    )]
+    pub migration_archive: Account<'info, MigrationArchive>,
+
+    pub new_reward_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl StartRewardMintMigration<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+        let config_change = &ctx.accounts.config_change;
+
+        let queued = config_change.new_migration.ok_or(error!(StakingError::NoQueuedMigration))?;
+        require!(
+            queued.new_mint.eq(&ctx.accounts.new_reward_mint.key()),
+            StakingError::InvalidMigrationConfig
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= config_change.eta, StakingError::ConfigChangeNotReady);
+
+        // Checked again, state may have changed since it was queued
+        require!(!global_pool.points_mode, StakingError::PointsMode);
+        require!(!global_pool.migration.active, StakingError::MigrationInProgress);
+        require!(!queued.new_mint.eq(&global_pool.reward_mint), StakingError::SameRewardMint);
+
+        let migration_archive = &mut ctx.accounts.migration_archive;
+        migration_archive.version = MigrationArchive::VERSION;
+        migration_archive.epoch = global_pool.reward_epoch;
+        migration_archive.migration = global_pool.migration.clone();
+        migration_archive.next_cutoff = now;
+
+        // Snapshot the liability in legacy mint, accrual in new mint starts from now
+        global_pool.accrue_liability(now);
+
+        global_pool.migration = RewardMintMigration {
+            legacy_mint: global_pool.reward_mint,
+            new_mint: queued.new_mint,
+            cutoff: now,
+            claim_deadline: now + queued.claim_window,
+            ratio_num: queued.ratio_num,
+            ratio_den: queued.ratio_den,
+            legacy_outstanding: global_pool.reward_liability,
+            active: true,
+        };
+        global_pool.reward_liability = 0;
+        global_pool.reward_epoch += 1;
+
+        let migration = &global_pool.migration;
+        emit!(RewardMintMigrationStarted {
+            legacy_mint: migration.legacy_mint,
+            new_mint: migration.new_mint,
+            cutoff: migration.cutoff,
+            claim_deadline: migration.claim_deadline,
+            ratio_num: migration.ratio_num,
+            ratio_den: migration.ratio_den,
+            legacy_outstanding: migration.legacy_outstanding,
+        });
+
+        Ok(())
+    }
+}
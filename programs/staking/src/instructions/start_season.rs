@@ -0,0 +1,53 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct StartSeason<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = operator @ StakingError::InvalidOperator,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    #[account(
+        init,
+        space = Season::DATA_SIZE,
+        seeds = [SEASON_SEED.as_ref(), global_pool.points_season.to_le_bytes().as_ref()],
+        bump,
+        payer = operator
+    )]
+    pub season: Account<'info, Season>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl StartSeason<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+        let season = &mut ctx.accounts.season;
+
+        require!(!global_pool.season_active, StakingError::SeasonActive);
+
+        let timestamp = Clock::get()?.unix_timestamp;
+
+        season.version = Season::VERSION;
+        season.id = global_pool.points_season;
+        season.start_time = timestamp;
+        season.active = true;
+        season.reward_per_day = global_pool.reward_per_day;
+
+        global_pool.season_active = true;
+
+        emit!(SeasonStarted {
+            operator: global_pool.operator,
+            season: season.id,
+            start_time: timestamp,
+        });
+
+        Ok(())
+    }
+}
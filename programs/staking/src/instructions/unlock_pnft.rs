@@ -1,8 +1,4 @@
-use {
-    crate::*, 
-    anchor_spl::token::{Mint, Token, TokenAccount}, 
-    mpl_token_metadata::instructions::{RevokeStakingV1CpiBuilder, UnlockV1CpiBuilder}
-};
+use crate::*;
 
 #[derive(Accounts)]
 pub struct UnlockPNFT<'info> {
@@ -14,98 +10,126 @@ pub struct UnlockPNFT<'info> {
         seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
         bump,
     )]
-    pub global_pool: Account<'info, GlobalPool>,
+    pub global_pool: Account<'info, Current<GlobalPool>>,
 
     #[account(
         mut,
         seeds = [USER_POOL_SEED.as_ref(), user.key().as_ref()],
-        bump
+        bump,
     )]
-    pub user_pool: Account<'info, UserPool>,
+    pub user_pool: Account<'info, Current<UserPool>>,
 
-    pub token_mint: Box<Account<'info, Mint>>,
-    #[account(
-        mut, 
-        token::mint = token_mint, 
-        token::authority = user,
-    )]    
-    pub token_account: Box<Account<'info, TokenAccount>>,
-    /// CHECK instruction will fail if wrong edition is supplied
-    pub token_mint_edition: AccountInfo<'info>,
-    /// CHECK instruction will fail if wrong record is supplied
-    #[account(mut)]
-    pub token_mint_record: AccountInfo<'info>,
-    /// CHECK instruction will fail if wrong metadata is supplied
-    #[account(mut)]
-    mint_metadata: UncheckedAccount<'info>,
-    /// CHECK instruction will fail if wrong rules are supplied
-    pub auth_rules: UncheckedAccount<'info>,
-    /// CHECK instruction will fail if wrong sysvar ixns are supplied
-    pub sysvar_instructions: AccountInfo<'info>,
-
-    pub token_program: Program<'info, Token>,
-    /// CHECK intstruction will fail if wrong program is supplied
-    pub token_metadata_program: AccountInfo<'info>,
-    /// CHECK intstruction will fail if wrong program is supplied
-    pub auth_rules_program: AccountInfo<'info>,
+    pub nft: UnlockNftAccounts<'info>,
     pub system_program: Program<'info, System>,
+
+    //  Receives the lock fee, required only if it's set
+    /// CHECK: address is checked against global_pool.treasury
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    //  Season accounts are required while a season is active
+    #[account(
+        mut,
+        seeds = [SEASON_SEED.as_ref(), global_pool.points_season.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub season: Option<Account<'info, Current<Season>>>,
+
+    #[account(
+        init_if_needed,
+        space = UserSeason::DATA_SIZE,
+        seeds = [USER_SEASON_SEED.as_ref(), global_pool.points_season.to_le_bytes().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+    )]
+    pub user_season: Option<Account<'info, UserSeason>>,
 }
 
-pub fn unlock_pnft_handler(ctx: Context<UnlockPNFT>) -> Result<()> {
+pub fn unlock_pnft_handler<'info>(ctx: Context<'_, '_, '_, 'info, UnlockPNFT<'info>>) -> Result<()> {
     let global_pool = &mut ctx.accounts.global_pool;
     let user_pool = &mut ctx.accounts.user_pool;
+    let nft = &ctx.accounts.nft;
+
+    require!(!global_pool.is_paused(PAUSE_UNLOCK), StakingError::UnlockPaused);
 
     // Check user pool owner matched with signed user
     require!(user_pool.owner.eq(&ctx.accounts.user.key()), StakingError::InvalidOwner);
 
-    let seeds = &[
-        GLOBAL_AUTHORITY_SEED.as_bytes(), 
-        &[ctx.bumps.global_pool]
-    ];
-    let delegate_seeds = &[&seeds[..]];
-
-    UnlockV1CpiBuilder::new(&ctx.accounts.token_metadata_program)
-        .authority(&global_pool.to_account_info().clone())
-        .token_owner(Some(&ctx.accounts.user))
-        .token(&ctx.accounts.token_account.to_account_info())
-        .mint(&ctx.accounts.token_mint.to_account_info())
-        .metadata(&ctx.accounts.mint_metadata)
-        .edition(Some(ctx.accounts.token_mint_edition.as_ref()))
-        .token_record(Some(ctx.accounts.token_mint_record.as_ref()))
-        .payer(&ctx.accounts.user)
-        .system_program(&ctx.accounts.system_program)
-        .sysvar_instructions(&ctx.accounts.sysvar_instructions)
-        .spl_token_program(Some(&ctx.accounts.token_program.to_account_info()))
-        .authorization_rules_program(Some(ctx.accounts.auth_rules_program.as_ref()))
-        .authorization_rules(Some(ctx.accounts.auth_rules.as_ref()))
-        .invoke_signed(delegate_seeds)?;
-
-    RevokeStakingV1CpiBuilder::new(&ctx.accounts.token_metadata_program)
-        .delegate(&global_pool.to_account_info())
-        .metadata(&ctx.accounts.mint_metadata.to_account_info())
-        .master_edition(Some(ctx.accounts.token_mint_edition.as_ref()))
-        .token_record(Some(ctx.accounts.token_mint_record.as_ref()))
-        .mint(&ctx.accounts.token_mint.to_account_info())
-        .token(&ctx.accounts.token_account.to_account_info())
-        .authority(&ctx.accounts.user)
-        .payer(&ctx.accounts.user)
-        .system_program(&ctx.accounts.system_program)
-        .sysvar_instructions(&ctx.accounts.sysvar_instructions)
-        .spl_token_program(Some(&ctx.accounts.token_program.to_account_info()))
-        .authorization_rules_program(Some(ctx.accounts.auth_rules_program.as_ref()))
-        .authorization_rules(Some(ctx.accounts.auth_rules.as_ref()))
-        .invoke()?;
+    // Settle rewards accrued before the last reward mint migration
+    user_pool.settle_reward_epoch(global_pool)?;
+
+    nft.unlock(
+        &global_pool.to_account_info(),
+        ctx.bumps.global_pool,
+        ctx.accounts.user.as_ref(),
+        ctx.accounts.user.as_ref(),
+        ctx.accounts.user.as_ref(),
+        ctx.accounts.system_program.as_ref(),
+    )?;
 
     let timestamp = Clock::get()?.unix_timestamp;
 
+    // Count the season accrual of the item before it's removed
+    checkpoint_season(
+        global_pool,
+        user_pool,
+        ctx.accounts.season.as_deref_mut().map(|season| &mut **season),
+        ctx.accounts.user_season.as_deref_mut(),
+        timestamp,
+    )?;
+
     // Validate if the nft staking exist and remove it
     user_pool.remove_nft(
-        ctx.accounts.token_mint.key(), 
+        nft.token_mint.key(), 
         timestamp, 
         global_pool.reward_per_day
         )?;
+
+    // Creators' royalty was kept out of the settled reward, it's credited to them until now
+    settle_stake_creators(global_pool, &nft.stake_creators, Some(timestamp), Some(&ctx.accounts.user.to_account_info()))?;
     
+    global_pool.accrue_liability(timestamp);
     global_pool.total_staked_count -= 1;
 
+    nft.burn_receipt_if_any(
+        global_pool.to_account_info(),
+        ctx.bumps.global_pool,
+        ctx.bumps.nft.receipt_mint,
+        ctx.accounts.user.to_account_info(),
+    )?;
+
+    shrink_user_pool(
+        user_pool.to_account_info(),
+        user_pool.item_count,
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    let lock_fee = collect_lock_fee(
+        &ctx.accounts.global_pool,
+        ctx.accounts.user.to_account_info(),
+        ctx.accounts.treasury.as_ref().map(|treasury| treasury.to_account_info()),
+        ctx.accounts.system_program.to_account_info(),
+    )?;
+    if lock_fee > 0 {
+        emit!(LockFeeCollected {
+            user: ctx.accounts.user.key(),
+            mint: nft.token_mint.key(),
+            action: HookAction::Unlock,
+            amount: lock_fee,
+        });
+    }
+
+    invoke_stake_hook(
+        &ctx.accounts.global_pool,
+        ctx.remaining_accounts,
+        StakeHookArgs {
+            owner: ctx.accounts.user.key(),
+            mint: nft.token_mint.key(),
+            action: HookAction::Unlock,
+            timestamp,
+        },
+    )?;
+
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,34 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = admin @ StakingError::InvalidAdmin,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+}
+
+impl Unpause<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, flags: u8) -> Result<()> {
+        let global_pool = &mut ctx.accounts.global_pool;
+
+        require!(flags != 0 && flags & !PAUSE_ALL == 0, StakingError::InvalidPauseFlags);
+
+        let old_flags = global_pool.pause_flags;
+        global_pool.pause_flags &= !flags;
+
+        emit!(PauseUpdated {
+            authority: ctx.accounts.admin.key(),
+            old_flags,
+            new_flags: global_pool.pause_flags,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,37 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct UpdateVoterWeightRecord<'info> {
+    #[account(mut)]
+    pub voter_weight_record: Account<'info, VoterWeightRecord>,
+
+    #[account(
+        seeds = [USER_POOL_SEED.as_ref(), voter_weight_record.governing_token_owner.as_ref()],
+        bump,
+    )]
+    pub user_pool: Account<'info, Current<UserPool>>,
+}
+
+/**
+ * Set voter weight from the owner's staked NFTs, valid only in the current slot
+ * so it must be updated in the same transaction as the governance action
+ * Votes go through cast_nft_vote, which records each NFT's vote on the proposal
+ */
+pub fn update_voter_weight_record_handler(
+    ctx: Context<UpdateVoterWeightRecord>,
+    voter_weight_action: VoterWeightAction,
+) -> Result<()> {
+    require!(voter_weight_action != VoterWeightAction::CastVote, StakingError::UseCastNftVote);
+
+    let voter_weight_record = &mut ctx.accounts.voter_weight_record;
+    let clock = Clock::get()?;
+
+    voter_weight_record.voter_weight = ctx.accounts.user_pool.voter_weight(clock.unix_timestamp);
+    voter_weight_record.voter_weight_expiry = Some(clock.slot);
+
+    // Weight only counts for the given action, on any target
+    voter_weight_record.weight_action = Some(voter_weight_action);
+    voter_weight_record.weight_action_target = None;
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use {
+    crate::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{self, Mint, Token, TokenAccount, Transfer},
+    },
+};
+
+#[derive(Accounts)]
+pub struct WithdrawLegacyVault<'info> {
+    #[account(mut)]
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = treasurer @ StakingError::InvalidTreasurer,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    //  Migration of an older reward epoch, the last one is read from the global pool
+    pub migration_archive: Option<Account<'info, MigrationArchive>>,
+
+    pub legacy_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = legacy_mint,
+        associated_token::authority = global_pool,
+    )]
+    pub legacy_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        associated_token::mint = legacy_mint,
+        associated_token::authority = treasurer,
+        payer = treasurer,
+    )]
+    pub treasurer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl WithdrawLegacyVault<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, amount: u64) -> Result<()> {
+        let global_pool = &ctx.accounts.global_pool;
+        let legacy_mint = ctx.accounts.legacy_mint.key();
+
+        // Legacy rewards left once the claim window closed are converted to the new mint on settle
+        let migration = match &ctx.accounts.migration_archive {
+            Some(migration_archive) => migration_archive.migration.clone(),
+            None => {
+                require!(!global_pool.migration.active, StakingError::ClaimWindowOpen);
+                global_pool.migration.clone()
+            }
+        };
+        require!(
+            !migration.legacy_mint.eq(&Pubkey::default())
+                && migration.legacy_mint.eq(&legacy_mint)
+                && !legacy_mint.eq(&global_pool.reward_mint),
+            StakingError::InvalidRewardMint
+        );
+
+        require!(
+            ctx.accounts.legacy_vault.amount >= amount,
+            StakingError::LackVaultBalance
+        );
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.global_pool]];
+        let signer = &[&seeds[..]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.legacy_vault.to_account_info(),
+            to: ctx.accounts.treasurer_token_account.to_account_info(),
+            authority: ctx.accounts.global_pool.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program, cpi_accounts, signer),
+            amount,
+        )?;
+
+        emit!(LegacyVaultWithdrawn {
+            treasurer: ctx.accounts.treasurer.key(),
+            legacy_mint,
+            amount,
+        });
+
+        Ok(())
+    }
+}
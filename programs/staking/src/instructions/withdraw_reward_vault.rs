@@ -0,0 +1,72 @@
+use {
+    crate::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{self, Mint, Token, TokenAccount, Transfer},
+    },
+};
+
+#[derive(Accounts)]
+pub struct WithdrawRewardVault<'info> {
+    #[account(mut)]
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_AUTHORITY_SEED.as_ref()],
+        bump,
+        has_one = treasurer @ StakingError::InvalidTreasurer,
+        has_one = reward_mint,
+    )]
+    pub global_pool: Account<'info, Current<GlobalPool>>,
+
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = global_pool,
+    )]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        associated_token::mint = reward_mint,
+        associated_token::authority = treasurer,
+        payer = treasurer,
+    )]
+    pub treasurer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl WithdrawRewardVault<'_> {
+    pub fn process_instruction(ctx: &mut Context<Self>, amount: u64) -> Result<()> {
+        // Validate reward vault balance enough
+        require!(
+            ctx.accounts.reward_vault.amount >= amount,
+            StakingError::LackVaultBalance
+        );
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[ctx.bumps.global_pool]];
+        let signer = &[&seeds[..]];
+        let token_program = ctx.accounts.token_program.to_account_info();
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.reward_vault.to_account_info(),
+            to: ctx.accounts.treasurer_token_account.to_account_info(),
+            authority: ctx.accounts.global_pool.to_account_info(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program, cpi_accounts, signer),
+            amount,
+        )?;
+
+        emit!(RewardVaultWithdrawn {
+            treasurer: ctx.accounts.treasurer.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
@@ -2,11 +2,13 @@ use anchor_lang::{prelude::*, AnchorDeserialize};
 
 pub mod constant;
 pub mod error;
+pub mod event;
 pub mod instructions;
 pub mod state;
 pub mod util;
 use constant::*;
 use error::*;
+use event::*;
 use instructions::*;
 use state::*;
 use util::*;
@@ -20,51 +22,316 @@ pub mod staking {
     /**
      * Initialize global pool
      * super admin sets to the caller of this instruction
+     * max_reward_per_day caps every later reward_per_day change
+     * points_mode credits claimed rewards as off-chain points instead of transferring reward token
      */
-    pub fn initialize(mut ctx: Context<Initialize>) -> Result<()> {
-        Initialize::process_instruction(&mut ctx)
+    pub fn initialize(mut ctx: Context<Initialize>, max_reward_per_day: u64, points_mode: bool) -> Result<()> {
+        Initialize::process_instruction(&mut ctx, max_reward_per_day, points_mode)
     }
 
-    //  Admin can config reward env
-    pub fn change_reward_env(
-        mut ctx: Context<ChangeRewardEnv>,
-        new_admin: Option<Pubkey>,
-        new_reward_mint: Option<Pubkey>,
+    /**
+     * Operator can queue reward env change, applied after the config delay
+     * new_fee_config sets the treasury and protocol fees, claim_fee_bps of each claimed reward
+     * and lock_fee lamports on each lock / unlock go to the treasury, no claim fee is taken in points mode
+     * new_creator_share_bps sets the share of staking reward paid to the verified creators of each staked NFT,
+     * creators are captured at lock and NFTs locked while it was zero pay no royalty
+     */
+    pub fn queue_config_change(
+        mut ctx: Context<QueueConfigChange>,
         new_reward_enable: Option<bool>,
         new_reward_per_day: Option<u64>,
+        new_config_delay: Option<i64>,
+        new_fee_config: Option<FeeConfig>,
+        new_creator_share_bps: Option<u16>,
     ) -> Result<()> {
-        ChangeRewardEnv::process_instruction(
+        QueueConfigChange::process_instruction(
             &mut ctx,
-            new_admin,
-            new_reward_mint,
             new_reward_enable,
             new_reward_per_day,
+            new_config_delay,
+            new_fee_config,
+            new_creator_share_bps,
         )
     }
 
-    //  Initialize user pool
-    pub fn init_user(mut ctx: Context<InitUser>) -> Result<()> {
-        InitUser::process_instruction(&mut ctx)
+    //  Anyone can apply the queued config change once its eta has passed
+    pub fn execute_config_change(mut ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        ExecuteConfigChange::process_instruction(&mut ctx)
+    }
+
+    //  Operator or admin can drop the queued config change
+    pub fn cancel_config_change(mut ctx: Context<CancelConfigChange>) -> Result<()> {
+        CancelConfigChange::process_instruction(&mut ctx)
+    }
+
+    //  Operator can queue switching reward mint, it can be started after the config delay
+    pub fn queue_reward_mint_migration(
+        mut ctx: Context<QueueRewardMintMigration>,
+        claim_window: i64,
+        ratio_num: u64,
+        ratio_den: u64,
+    ) -> Result<()> {
+        QueueRewardMintMigration::process_instruction(&mut ctx, claim_window, ratio_num, ratio_den)
+    }
+
+    //  Anyone can start the queued reward mint migration once its eta has passed
+    //  rewards accrued so far stay in legacy mint
+    pub fn start_reward_mint_migration(mut ctx: Context<StartRewardMintMigration>) -> Result<()> {
+        StartRewardMintMigration::process_instruction(&mut ctx)
+    }
+
+    //  Anyone can switch to new reward mint once legacy claim window is over
+    pub fn finalize_reward_mint_migration(mut ctx: Context<FinalizeRewardMintMigration>) -> Result<()> {
+        FinalizeRewardMintMigration::process_instruction(&mut ctx)
+    }
+
+    //  Anyone can settle a user's rewards accrued before the last reward mint migration
+    //  archives of skipped migrations are passed as remaining accounts
+    pub fn settle_reward_epoch<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, SettleRewardEpoch<'info>>,
+    ) -> Result<()> {
+        SettleRewardEpoch::process_instruction(&mut ctx)
+    }
+
+    //  Admin can propose a new admin, which takes effect once accepted
+    pub fn propose_admin(mut ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        ProposeAdmin::process_instruction(&mut ctx, new_admin)
+    }
+
+    //  Pending admin accepts the admin transfer
+    pub fn accept_admin(mut ctx: Context<AcceptAdmin>) -> Result<()> {
+        AcceptAdmin::process_instruction(&mut ctx)
+    }
+
+    //  Admin can cancel the pending admin transfer
+    pub fn cancel_admin_transfer(mut ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        CancelAdminTransfer::process_instruction(&mut ctx)
+    }
+
+    //  Admin can assign operator, pauser and treasurer roles
+    pub fn set_role(mut ctx: Context<SetRole>, role: Role, new_key: Pubkey) -> Result<()> {
+        SetRole::process_instruction(&mut ctx, role, new_key)
+    }
+
+    //  Treasurer can deposit reward token to the vault
+    pub fn fund_reward_vault(mut ctx: Context<FundRewardVault>, amount: u64) -> Result<()> {
+        FundRewardVault::process_instruction(&mut ctx, amount)
+    }
+
+    //  Treasurer can withdraw reward token from the vault
+    pub fn withdraw_reward_vault(mut ctx: Context<WithdrawRewardVault>, amount: u64) -> Result<()> {
+        WithdrawRewardVault::process_instruction(&mut ctx, amount)
+    }
+
+    //  Pauser can pause lock / claim / unlock separately with PAUSE_* flags, unlock only in emergency mode
+    pub fn pause(mut ctx: Context<Pause>, flags: u8) -> Result<()> {
+        Pause::process_instruction(&mut ctx, flags)
+    }
+
+    //  Admin can clear PAUSE_* flags
+    pub fn unpause(mut ctx: Context<Unpause>, flags: u8) -> Result<()> {
+        Unpause::process_instruction(&mut ctx, flags)
+    }
+
+    //  Admin can turn emergency mode on / off
+    pub fn set_emergency_mode(mut ctx: Context<SetEmergencyMode>, enabled: bool) -> Result<()> {
+        SetEmergencyMode::process_instruction(&mut ctx, enabled)
+    }
+
+    /**
+     * Admin can set the program notified after lock / unlock / claim, None turns it off
+     * Emergency and force unlocks never call the hook
+     */
+    pub fn set_hook_program(mut ctx: Context<SetHookProgram>, hook_program: Option<Pubkey>) -> Result<()> {
+        SetHookProgram::process_instruction(&mut ctx, hook_program)
+    }
+
+    //  Operator spends user's points for an off-chain redemption
+    pub fn spend_points(mut ctx: Context<SpendPoints>, amount: u64, redemption_id: u64) -> Result<()> {
+        SpendPoints::process_instruction(&mut ctx, amount, redemption_id)
+    }
+
+    //  Operator sets referral bonus bps and the total bonus cap per referrer
+    pub fn set_referral_config(mut ctx: Context<SetReferralConfig>, referral_bps: u16, referral_cap: u64) -> Result<()> {
+        SetReferralConfig::process_instruction(&mut ctx, referral_bps, referral_cap)
+    }
+
+    //  Operator starts a season, rewards accrued by staked NFTs during it are totaled per user in UserSeason PDAs
+    //  at the reward rate of its start, claimed or not
+    pub fn start_season(mut ctx: Context<StartSeason>) -> Result<()> {
+        StartSeason::process_instruction(&mut ctx)
+    }
+
+    //  Operator ends the active season and archives its stats in the Season account
+    pub fn end_season(mut ctx: Context<EndSeason>) -> Result<()> {
+        EndSeason::process_instruction(&mut ctx)
+    }
+
+    //  Anyone can count a user's reward accrued until the end of a season the user pool is behind
+    pub fn settle_user_season(mut ctx: Context<SettleUserSeason>) -> Result<()> {
+        SettleUserSeason::process_instruction(&mut ctx)
+    }
+
+    /**
+     * Initialize user pool
+     * referrer earns referral_bps of the user's claims, it can't be changed later
+     */
+    pub fn init_user(mut ctx: Context<InitUser>, referrer: Option<Pubkey>) -> Result<()> {
+        InitUser::process_instruction(&mut ctx, referrer)
     }
 
     /**
      * User can lock pNFTs from specific collection
+     * user pool is initialized on the first lock
      */
-    pub fn lock_pnft(ctx: Context<LockPNFT>) -> Result<()> {
+    pub fn lock_pnft<'info>(ctx: Context<'_, '_, '_, 'info, LockPNFT<'info>>) -> Result<()> {
         lock_pnft::lock_pnft_handler(ctx)
     }
 
     /**
-     * User can claim reward
+     * User can claim reward, creators' royalty captured at lock is kept out of it
+     * remaining accounts are only the hook accounts, see invoke_stake_hook
      */
-    pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
+    pub fn claim_reward<'info>(ctx: Context<'_, '_, '_, 'info, ClaimReward<'info>>) -> Result<()> {
         claim_reward::claim_reward_handler(ctx)
     }
 
+    /**
+     * Creator can claim its royalty accrued on a staked NFT, staker's claims don't settle it
+     */
+    pub fn claim_creator_royalty(ctx: Context<ClaimCreatorRoyalty>) -> Result<()> {
+        claim_creator_royalty::claim_creator_royalty_handler(ctx)
+    }
+
+    /**
+     * User can claim reward accrued before migration cutoff in legacy mint
+     */
+    pub fn claim_legacy_reward(ctx: Context<ClaimLegacyReward>) -> Result<()> {
+        claim_legacy_reward::claim_legacy_reward_handler(ctx)
+    }
+
     /**
      * User can unlock pNFTs when they want
      */
-    pub fn unlock_pnft(ctx: Context<UnlockPNFT>) -> Result<()> {
+    pub fn unlock_pnft<'info>(ctx: Context<'_, '_, '_, 'info, UnlockPNFT<'info>>) -> Result<()> {
         unlock_pnft::unlock_pnft_handler(ctx)
     }
+
+    /**
+     * Anyone can unlock a pNFT back to its owner in emergency mode
+     * no reward is settled for the unlocked NFT
+     */
+    pub fn emergency_unlock(ctx: Context<EmergencyUnlock>) -> Result<()> {
+        emergency_unlock::emergency_unlock_handler(ctx)
+    }
+
+    /**
+     * Admin can unlock a user's pNFT back to its owner
+     * reward of the NFT is settled to owner's pending reward
+     */
+    pub fn force_unlock(ctx: Context<ForceUnlock>) -> Result<()> {
+        force_unlock::force_unlock_handler(ctx)
+    }
+
+    /**
+     * User can drop stale entries from the user pool
+     * remaining accounts are [token_account, token_mint_record] pairs for each staked item,
+     * token_account is the one holding the NFT now
+     */
+    pub fn reconcile_user_pool(ctx: Context<ReconcileUserPool>) -> Result<()> {
+        reconcile_user_pool::reconcile_user_pool_handler(ctx)
+    }
+
+    /**
+     * Read-only view of rewards claimable now, returned through return data
+     * Simulate it to get per-NFT rewards from offset and the total
+     */
+    pub fn get_pending_reward(ctx: Context<GetPendingReward>, offset: u32) -> Result<PendingReward> {
+        get_pending_reward::get_pending_reward_handler(ctx, offset)
+    }
+
+    /**
+     * spl-governance voter weight addin, so a realm can use staked NFTs as voting power
+     * Anyone can create the record of a governing token owner for a realm
+     */
+    pub fn create_voter_weight_record(
+        ctx: Context<CreateVoterWeightRecord>,
+        governing_token_owner: Pubkey,
+    ) -> Result<()> {
+        create_voter_weight_record::create_voter_weight_record_handler(ctx, governing_token_owner)
+    }
+
+    //  Anyone can refresh voter weight for an action other than CastVote, it expires after the current slot
+    pub fn update_voter_weight_record(
+        ctx: Context<UpdateVoterWeightRecord>,
+        voter_weight_action: VoterWeightAction,
+    ) -> Result<()> {
+        update_voter_weight_record::update_voter_weight_record_handler(ctx, voter_weight_action)
+    }
+
+    /**
+     * Owner sets CastVote weight for a proposal from staked NFTs that haven't voted on it yet
+     * NFT vote record PDAs of items[offset..] are passed as remaining accounts
+     */
+    pub fn cast_nft_vote<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, CastNftVote<'info>>,
+        proposal: Pubkey,
+        offset: u32,
+    ) -> Result<()> {
+        CastNftVote::process_instruction(&mut ctx, proposal, offset)
+    }
+
+    //  Owner closes NFT vote records of a proposal that is no longer voting and gets their rent back
+    pub fn relinquish_nft_vote<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, RelinquishNftVote<'info>>,
+    ) -> Result<()> {
+        RelinquishNftVote::process_instruction(&mut ctx)
+    }
+
+    /**
+     * Read-only CPI surface for partner programs, enable the `cpi` feature to call it
+     * assert_staked fails with NotStaked unless the mint is staked in the user pool
+     */
+    pub fn assert_staked(ctx: Context<AssertStaked>, mint: Pubkey) -> Result<StakeStatus> {
+        assert_staked::assert_staked_handler(ctx, mint)
+    }
+
+    //  Number of NFTs staked by owner, returned through return data
+    pub fn staked_count(ctx: Context<StakedCount>, owner: Pubkey) -> Result<u64> {
+        staked_count::staked_count_handler(ctx, owner)
+    }
+
+    //  Close user pool once all NFTs are unlocked, rewards are claimed, points are spent and seasons are settled
+    //  Referrers who earned referral bonus keep their pool, so its cap can't be reset
+    pub fn close_user_pool(mut ctx: Context<CloseUserPool>) -> Result<()> {
+        CloseUserPool::process_instruction(&mut ctx)
+    }
+
+    /**
+     * Admin can migrate the global pool from the unversioned or version 1 layout
+     * new roles of unversioned pools start with the admin and max_reward_per_day sets their reward cap,
+     * version 1 pools keep theirs
+     */
+    pub fn migrate_global_pool(mut ctx: Context<MigrateGlobalPool>, max_reward_per_day: u64) -> Result<()> {
+        MigrateGlobalPool::process_instruction(&mut ctx, max_reward_per_day)
+    }
+
+    //  Anyone can migrate a user pool from the unversioned or version 1 layout
+    pub fn migrate_user_pool(ctx: Context<MigrateUserPool>) -> Result<()> {
+        migrate_user_pool::migrate_user_pool_handler(ctx)
+    }
+
+    //  Treasurer can withdraw what is left in the vault of a legacy reward mint once its claim window is closed
+    //  pass the migration archive of an older reward epoch to withdraw its legacy mint
+    pub fn withdraw_legacy_vault(mut ctx: Context<WithdrawLegacyVault>, amount: u64) -> Result<()> {
+        WithdrawLegacyVault::process_instruction(&mut ctx, amount)
+    }
+
+    //  Anyone can settle creators' royalty of an NFT accrued before the last reward mint migration
+    //  archives of skipped migrations are passed as remaining accounts
+    pub fn settle_royalty_epoch<'info>(
+        mut ctx: Context<'_, '_, '_, 'info, SettleRoyaltyEpoch<'info>>,
+    ) -> Result<()> {
+        SettleRoyaltyEpoch::process_instruction(&mut ctx)
+    }
 }
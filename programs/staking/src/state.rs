@@ -1,14 +1,107 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, Discriminator};
 
 use crate::constant::*;
 use crate::error::*;
+
+/**
+ * Account with a version byte right after the anchor discriminator
+ */
+pub trait Versioned: AccountSerialize + AccountDeserialize + Discriminator + Owner + Clone {
+    const VERSION: u8;
+}
+
+/**
+ * Versioned account of the current layout, loaded as Account<Current<T>>
+ * The version byte is checked before the layout, so older accounts fail with InvalidAccountVersion
+ */
+#[derive(Clone)]
+pub struct Current<T: Versioned>(T);
+
+impl<T: Versioned> Current<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Versioned> AccountDeserialize for Current<T> {
+    fn try_deserialize(buf: &mut &[u8]) -> Result<Self> {
+        if buf.len() > 8 && buf[..8] == T::DISCRIMINATOR {
+            require!(buf[8] == T::VERSION, StakingError::InvalidAccountVersion);
+        }
+        T::try_deserialize(buf).map(Current)
+    }
+
+    fn try_deserialize_unchecked(buf: &mut &[u8]) -> Result<Self> {
+        T::try_deserialize_unchecked(buf).map(Current)
+    }
+}
+
+impl<T: Versioned> AccountSerialize for Current<T> {
+    fn try_serialize<W: std::io::Write>(&self, writer: &mut W) -> Result<()> {
+        self.0.try_serialize(writer)
+    }
+}
+
+impl<T: Versioned> Owner for Current<T> {
+    fn owner() -> Pubkey {
+        T::owner()
+    }
+}
+
+impl<T: Versioned> std::ops::Deref for Current<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Versioned> std::ops::DerefMut for Current<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/**
+ * Global staking state, PDA of [GLOBAL_AUTHORITY_SEED]
+ * Layout is stable within a VERSION, any breaking change bumps it
+ */
 #[account]
+#[derive(InitSpace)]
 pub struct GlobalPool {
+    pub version: u8,
     pub admin: Pubkey,
     pub reward_per_day: u64,
     pub reward_mint: Pubkey,
     pub reward_enable: bool,
     pub total_staked_count: u64,
+    pub pending_admin: Pubkey,
+    pub operator: Pubkey,
+    pub pauser: Pubkey,
+    pub treasurer: Pubkey,
+    pub pause_flags: u8,
+    pub config_delay: i64,
+    pub emergency_mode: bool,
+    pub reward_epoch: u16,
+    pub reward_liability: u64,
+    pub liability_time: i64,
+    pub migration: RewardMintMigration,
+    pub reward_decimals: u8,
+    pub max_reward_per_day: u64,
+    pub hook_program: Pubkey,
+    pub points_mode: bool,
+    // Id of the active season, or of the next one if none is active
+    pub points_season: u16,
+    pub season_active: bool,
+    // Referrer bonus in bps of referred users' claims, capped per referrer by referral_cap
+    pub referral_bps: u16,
+    pub referral_cap: u64,
+    // Protocol fees, claim fee is taken from reward token and lock fee in lamports on lock / unlock
+    pub treasury: Pubkey,
+    pub claim_fee_bps: u16,
+    pub lock_fee: u64,
+    // Share of staking reward paid to the verified creators of each staked NFT
+    pub creator_share_bps: u16,
     pub extra: u128,
 }
 
@@ -16,34 +109,328 @@ impl Default for GlobalPool {
     #[inline]
     fn default() -> GlobalPool {
         GlobalPool {
+            version: GlobalPool::VERSION,
             admin: Pubkey::default(),
             reward_per_day: 0,
             reward_mint: Pubkey::default(),
             reward_enable: false,
             total_staked_count: 0,
+            pending_admin: Pubkey::default(),
+            operator: Pubkey::default(),
+            pauser: Pubkey::default(),
+            treasurer: Pubkey::default(),
+            pause_flags: 0,
+            config_delay: 0,
+            emergency_mode: false,
+            reward_epoch: 0,
+            reward_liability: 0,
+            liability_time: 0,
+            migration: RewardMintMigration::default(),
+            reward_decimals: 0,
+            max_reward_per_day: 0,
+            hook_program: Pubkey::default(),
+            points_mode: false,
+            points_season: 0,
+            season_active: false,
+            referral_bps: 0,
+            referral_cap: 0,
+            treasury: Pubkey::default(),
+            claim_fee_bps: 0,
+            lock_fee: 0,
+            creator_share_bps: 0,
             extra: 0,
         }
     }
 }
 
+impl Versioned for GlobalPool {
+    const VERSION: u8 = 2;
+}
+
 impl GlobalPool {
-    pub const DATA_SIZE: usize = 8 + std::mem::size_of::<GlobalPool>();
+    pub const DATA_SIZE: usize = 8 + GlobalPool::INIT_SPACE;
+
+    /**
+     * Accrue outstanding reward liability in the current reward mint
+     * Should be called before total_staked_count or reward_per_day changes
+     * Nothing is owed in reward token in points mode
+     */
+    pub fn accrue_liability(&mut self, now: i64) {
+        if !self.points_mode && self.liability_time != 0 && now > self.liability_time {
+            let accrued = (now - self.liability_time) as u128
+                * self.total_staked_count as u128
+                * self.reward_per_day as u128
+                / DAY as u128;
+            self.reward_liability = self.reward_liability.saturating_add(accrued as u64);
+        }
+        self.liability_time = now;
+    }
+
+    pub fn referral_bonus(&self, reward: u64) -> u64 {
+        (reward as u128 * self.referral_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    pub fn claim_fee(&self, reward: u64) -> u64 {
+        (reward as u128 * self.claim_fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+
+    pub fn fee_config(&self) -> FeeConfig {
+        FeeConfig {
+            treasury: self.treasury,
+            claim_fee_bps: self.claim_fee_bps,
+            lock_fee: self.lock_fee,
+        }
+    }
+
+    pub fn is_paused(&self, flag: u8) -> bool {
+        self.pause_flags & flag != 0
+    }
+
+    pub fn role(&self, role: Role) -> Pubkey {
+        match role {
+            Role::Operator => self.operator,
+            Role::Pauser => self.pauser,
+            Role::Treasurer => self.treasurer,
+        }
+    }
+
+    pub fn set_role(&mut self, role: Role, key: Pubkey) {
+        match role {
+            Role::Operator => self.operator = key,
+            Role::Pauser => self.pauser = key,
+            Role::Treasurer => self.treasurer = key,
+        }
+    }
+}
+
+/**
+ * Reward mint switch in progress (active) or last finalized one
+ * Rewards accrued before cutoff stay in legacy mint and can be claimed until claim_deadline,
+ * after that they are converted to new mint with ratio_num / ratio_den
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Default, Clone)]
+pub struct RewardMintMigration {
+    pub legacy_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub cutoff: i64,
+    pub claim_deadline: i64,
+    pub ratio_num: u64,
+    pub ratio_den: u64,
+    pub legacy_outstanding: u64,
+    pub active: bool,
+}
+
+/**
+ * Reward mint migration queued with queue_reward_mint_migration
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MigrationConfig {
+    pub new_mint: Pubkey,
+    pub claim_window: i64,
+    pub ratio_num: u64,
+    pub ratio_den: u64,
+}
+
+impl MigrationConfig {
+    pub fn is_valid(&self) -> bool {
+        let (num, den) = (self.ratio_num as u128, self.ratio_den as u128);
+        num > 0
+            && den > 0
+            && num <= den * MAX_MIGRATION_RATIO as u128
+            && den <= num * MAX_MIGRATION_RATIO as u128
+            && (MIN_MIGRATION_CLAIM_WINDOW..=MAX_MIGRATION_CLAIM_WINDOW).contains(&self.claim_window)
+    }
+}
+
+/**
+ * Treasury and protocol fees, queued with queue_config_change
+ * claim_fee_bps of each claimed reward and lock_fee lamports on each lock / unlock go to the treasury
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeeConfig {
+    pub treasury: Pubkey,
+    pub claim_fee_bps: u16,
+    pub lock_fee: u64,
+}
+
+impl FeeConfig {
+    pub fn is_valid(&self) -> bool {
+        self.claim_fee_bps <= MAX_CLAIM_FEE_BPS
+            && self.lock_fee <= MAX_LOCK_FEE
+            && (!self.treasury.eq(&Pubkey::default()) || (self.claim_fee_bps == 0 && self.lock_fee == 0))
+    }
+}
+
+/**
+ * Reward mint migration of a past reward epoch, PDA of [MIGRATION_ARCHIVE_SEED, epoch]
+ * Written when the next migration starts, next_cutoff is the cutoff of that migration
+ * legacy_outstanding is frozen at that time, users who skipped it are settled from the archive
+ */
+#[account]
+#[derive(InitSpace, Default)]
+pub struct MigrationArchive {
+    pub version: u8,
+    pub epoch: u16,
+    pub migration: RewardMintMigration,
+    pub next_cutoff: i64,
+}
+
+impl Versioned for MigrationArchive {
+    const VERSION: u8 = 1;
+}
+
+impl MigrationArchive {
+    pub const DATA_SIZE: usize = 8 + MigrationArchive::INIT_SPACE;
+}
+
+/**
+ * Roles managed by the admin (owner) of the global pool
+ * Operator tunes reward config, pauser can only pause, treasurer funds and withdraws the vault
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Role {
+    Operator,
+    Pauser,
+    Treasurer,
+}
+
+/**
+ * Action reported to the hook program
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HookAction {
+    Lock,
+    Unlock,
+    Claim,
+}
+
+/**
+ * Instruction data of the hook CPI, after STAKE_HOOK_DISCRIMINATOR
+ * mint is default for claims
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StakeHookArgs {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub action: HookAction,
+    pub timestamp: i64,
+}
+
+/**
+ * Reward config change queued by the operator
+ * It can be executed once eta has passed, so stakers can see it coming
+ */
+#[account]
+#[derive(InitSpace, Default)]
+pub struct ConfigChange {
+    pub version: u8,
+    pub proposer: Pubkey,
+    pub new_reward_enable: Option<bool>,
+    pub new_reward_per_day: Option<u64>,
+    pub new_config_delay: Option<i64>,
+    pub new_fee_config: Option<FeeConfig>,
+    pub new_creator_share_bps: Option<u16>,
+    pub queued_at: i64,
+    pub eta: i64,
+    // Set only by queue_reward_mint_migration, such a change is applied by start_reward_mint_migration
+    pub new_migration: Option<MigrationConfig>,
+}
+
+impl Versioned for ConfigChange {
+    const VERSION: u8 = 2;
+}
+
+impl ConfigChange {
+    pub const DATA_SIZE: usize = 8 + ConfigChange::INIT_SPACE;
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Default, Clone)]
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Default, Clone)]
 pub struct StakedNFT {
     pub nft_addr: Pubkey,
     pub stake_time: i64,
-    pub extra: u128, // tbh for extra setting
+    // Creator share captured at lock, zero if the NFT has no verified creators
+    pub creator_share_bps: u16,
+    pub extra: [u8; 14], // tbh for extra setting
+}
+
+impl StakedNFT {
+    /**
+     * Part of the item reward credited to its creators by StakeCreators::accrue
+     */
+    pub fn royalty(&self, reward: u64) -> u64 {
+        (reward as u128 * self.creator_share_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    }
+}
+
+/**
+ * Return data of get_pending_reward
+ * total is accrued before creator royalty, net is what claim_reward pays the user after creator royalty and claim fee
+ * item rewards are net of their creator royalty
+ * legacy_reward is claimable by claim_legacy_reward
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingReward {
+    pub now: i64,
+    pub item_count: u64,
+    pub pending_reward: u64,
+    pub legacy_reward: u64,
+    pub total: u64,
+    pub creator_royalty: u64,
+    pub claim_fee: u64,
+    pub net: u64,
+    pub items: Vec<ItemReward>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ItemReward {
+    pub nft_addr: Pubkey,
+    pub reward: u64,
+}
+
+/**
+ * Return data of assert_staked
+ */
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct StakeStatus {
+    pub owner: Pubkey,
+    pub nft_addr: Pubkey,
+    pub stake_time: i64,
 }
 
+/**
+ * Per-user staking state, PDA of [USER_POOL_SEED, owner]
+ * Layout is stable within a VERSION so partners can read it directly:
+ * fields are never reordered or retyped, only items[..item_count] are live entries
+ */
 #[account]
+#[derive(InitSpace)]
 pub struct UserPool {
+    pub version: u8,
     pub owner: Pubkey,
     pub item_count: u64,
     pub reward_time: i64,
     pub pending_reward: u64,
+    pub reward_epoch: u16,
+    pub legacy_reward: u64,
+    // Points mode counters, points is cumulative and never decreases
+    pub points: u64,
+    pub points_spent: u64,
+    pub points_season: u16,
+    pub season_points: u64,
+    pub last_season_points: u64,
+    // Default pubkey when the user wasn't referred
+    pub referrer: Pubkey,
+    // Part of pending_reward credited as referral bonus, it doesn't earn a bonus itself
+    pub referral_bonus: u64,
+    // Total referral bonus ever credited, checked against referral_cap
+    pub referral_earned: u64,
+    // Season whose accrual is counted into UserSeason until season_time, behind points_season until
+    // the ended seasons are settled with settle_user_season
+    pub season: u16,
+    pub season_time: i64,
     pub extra: u128,
+    // Only the vec length prefix is counted in INIT_SPACE, items are added by size_calc
+    #[max_len(0)]
     pub items: Vec<StakedNFT>,
 }
 
@@ -51,46 +438,73 @@ impl Default for UserPool {
     #[inline]
     fn default() -> UserPool {
         UserPool {
+            version: UserPool::VERSION,
             owner: Pubkey::default(),
             item_count: 0,
             reward_time: 0,
             pending_reward: 0,
+            reward_epoch: 0,
+            legacy_reward: 0,
+            points: 0,
+            points_spent: 0,
+            points_season: 0,
+            season_points: 0,
+            last_season_points: 0,
+            referrer: Pubkey::default(),
+            referral_bonus: 0,
+            referral_earned: 0,
+            season: 0,
+            season_time: 0,
             extra: 0,
             items: Vec::with_capacity(DEFAULT_STAKE_SIZE),
         }
     }
 }
 
+impl Versioned for UserPool {
+    const VERSION: u8 = 2;
+}
+
 impl UserPool {
-    pub const STAKING_SIZE: usize = std::mem::size_of::<StakedNFT>();
-    pub const INIT_SIZE: usize = 8 + 32 + 8 + 8 + 8 + 16 + 4;
+    pub const STAKING_SIZE: usize = StakedNFT::INIT_SPACE;
+    pub const INIT_SIZE: usize = 8 + UserPool::INIT_SPACE;
 
     pub fn size_calc(count: u64) -> usize {
         UserPool::INIT_SIZE + UserPool::STAKING_SIZE * (count as usize)
     }
 
-    pub fn add_nft(&mut self, item: StakedNFT) {
+    /**
+     * Check the account has no room for one more item
+     * Based on the account data length, vec capacity after deserialization is unrelated
+     */
+    pub fn is_full(&self, data_len: usize) -> bool {
+        data_len < UserPool::size_calc(self.item_count + 1)
+    }
+
+    pub fn add_nft(&mut self, item: StakedNFT) -> Result<()> {
+        // Drop stale entries left past item_count by older versions
+        self.items.truncate(self.item_count as usize);
+        require!(
+            !self.items.iter().any(|staked| staked.nft_addr.eq(&item.nft_addr)),
+            StakingError::AlreadyStaked
+        );
         self.items.push(item);
         self.item_count += 1;
+        Ok(())
     }
 
     pub fn remove_nft(&mut self, nft_mint: Pubkey, now: i64, reward_per_day: u64) -> Result<u64> {
+        self.items.truncate(self.item_count as usize);
+
         let mut withdrawn: u8 = 0;
         let mut reward: u64 = 0;
         for i in 0..self.item_count {
             let index = i as usize;
             if self.items[index].nft_addr.eq(&nft_mint) {
-                let mut last_reward_time: i64 = self.items[index].stake_time;
-                if last_reward_time < self.reward_time {
-                    last_reward_time = self.reward_time
-                }
-                reward = ((now as u128 - last_reward_time as u128) * reward_per_day as u128 / DAY as u128) as u64;
+                reward = self.item_reward(&self.items[index], now, reward_per_day);
 
                 // remove nft
-                if i != self.item_count - 1 {
-                    let last_idx = self.item_count - 1;
-                    self.items[index] = self.items[last_idx as usize].clone();
-                }
+                self.items.swap_remove(index);
                 self.item_count -= 1;
                 self.pending_reward = self.pending_reward.checked_add(reward).unwrap();
                 withdrawn = 1;
@@ -101,20 +515,886 @@ impl UserPool {
         Ok(reward)
     }
 
-    pub fn claim_reward(&mut self, now: i64, reward_per_day: u64) -> Result<u64> {
-        let mut reward: u64 = 0;
-        for i in 0..self.item_count {
-            let index = i as usize;
-            let mut last_reward_time: i64 = self.items[index].stake_time;
-            if last_reward_time < self.reward_time {
-                last_reward_time = self.reward_time
-            }
-            reward = ((now as u128 - last_reward_time as u128) * reward_per_day as u128 / DAY as u128) as u64;
+    pub fn drop_nft(&mut self, nft_mint: Pubkey) -> Result<()> {
+        self.items.truncate(self.item_count as usize);
+
+        // remove nft without settling its reward
+        let index = self
+            .items
+            .iter()
+            .position(|staked| staked.nft_addr.eq(&nft_mint))
+            .ok_or(error!(StakingError::InvalidNFTAddress))?;
+        self.items.swap_remove(index);
+        self.item_count -= 1;
+        Ok(())
+    }
+
+    /**
+     * Governance voting power of staked items, see VOTER_WEIGHT_* constants
+     */
+    pub fn voter_weight(&self, now: i64) -> u64 {
+        self.items
+            .iter()
+            .take(self.item_count as usize)
+            .map(|item| self.item_voter_weight(item, now))
+            .fold(0u64, |total, weight| total.checked_add(weight).unwrap())
+    }
+
+    pub fn item_voter_weight(&self, item: &StakedNFT, now: i64) -> u64 {
+        let duration = now.saturating_sub(item.stake_time).clamp(0, VOTER_WEIGHT_MAX_DURATION);
+        let bonus = VOTER_WEIGHT_PER_NFT as u128 * duration as u128 / VOTER_WEIGHT_MAX_DURATION as u128;
+        VOTER_WEIGHT_PER_NFT.checked_add(bonus as u64).unwrap()
+    }
+
+    /**
+     * Reward accrued by a staked item from reward_time until given time, its creators' royalty is kept out
+     */
+    pub fn item_reward(&self, item: &StakedNFT, until: i64, reward_per_day: u64) -> u64 {
+        let reward = self.item_accrual(item, until, reward_per_day);
+        reward - item.royalty(reward)
+    }
+
+    /**
+     * Creators' royalty of a staked item from reward_time until given time
+     */
+    pub fn item_royalty(&self, item: &StakedNFT, until: i64, reward_per_day: u64) -> u64 {
+        item.royalty(self.item_accrual(item, until, reward_per_day))
+    }
+
+    fn item_accrual(&self, item: &StakedNFT, until: i64, reward_per_day: u64) -> u64 {
+        let last_reward_time = item.stake_time.max(self.reward_time);
+        if until > last_reward_time {
+            ((until - last_reward_time) as u128 * reward_per_day as u128 / DAY as u128) as u64
+        } else {
+            0
+        }
+    }
+
+    /**
+     * Reward accrued by staked items from reward_time until given time
+     */
+    pub fn accrued_reward(&self, until: i64, reward_per_day: u64) -> u64 {
+        self.items
+            .iter()
+            .take(self.item_count as usize)
+            .map(|item| self.item_reward(item, until, reward_per_day))
+            .fold(0u64, |total, reward| total.checked_add(reward).unwrap())
+    }
+
+    /**
+     * Creators' royalty kept out of accrued_reward
+     */
+    pub fn accrued_royalty(&self, until: i64, reward_per_day: u64) -> u64 {
+        self.items
+            .iter()
+            .take(self.item_count as usize)
+            .map(|item| self.item_royalty(item, until, reward_per_day))
+            .fold(0u64, |total, royalty| total.checked_add(royalty).unwrap())
+    }
+
+    /**
+     * Reward accrued by staked items between from and until, claimed or not
+     * Items must not change in between, so it's counted before every unlock
+     */
+    pub fn season_reward(&self, from: i64, until: i64, reward_per_day: u64) -> u64 {
+        self.items
+            .iter()
+            .take(self.item_count as usize)
+            .map(|item| {
+                let start = item.stake_time.max(from);
+                if until > start {
+                    ((until - start) as u128 * reward_per_day as u128 / DAY as u128) as u64
+                } else {
+                    0
+                }
+            })
+            .fold(0u64, |total, reward| total.checked_add(reward).unwrap())
+    }
+
+    /**
+     * Move rewards accrued before the last reward mint migration cutoff into legacy_reward
+     * and convert them to the new mint once the migration is finalized
+     * Users who skipped a whole migration must be settled from its archive by settle_archived_epoch first
+     */
+    pub fn settle_reward_epoch(&mut self, global_pool: &mut GlobalPool) -> Result<()> {
+        if self.reward_epoch < global_pool.reward_epoch {
+            // legacy_reward of an older epoch can only be converted with its own migration ratio
+            require!(
+                self.reward_epoch + 1 == global_pool.reward_epoch && self.legacy_reward == 0,
+                StakingError::RewardEpochNotSettled
+            );
+            self.start_reward_epoch(
+                global_pool.reward_epoch,
+                global_pool.migration.cutoff,
+                global_pool.reward_per_day,
+            );
         }
 
-        reward = reward.checked_add(self.pending_reward).unwrap();
+        let migration = &mut global_pool.migration;
+        if !migration.active && self.legacy_reward > 0 {
+            let legacy = self.legacy_reward;
+            let converted = self.convert_legacy_reward(migration);
+            migration.legacy_outstanding = migration.legacy_outstanding.saturating_sub(legacy);
+            global_pool.reward_liability = global_pool.reward_liability.saturating_add(converted);
+        }
+        Ok(())
+    }
+
+    /**
+     * Settle the reward epoch of the given archive and move to the next one
+     * Legacy rewards are converted with the archived ratio, rewards accrued until next cutoff become legacy
+     */
+    pub fn settle_archived_epoch(&mut self, archive: &MigrationArchive, reward_per_day: u64) -> Result<()> {
+        require!(archive.epoch == self.reward_epoch, StakingError::InvalidMigrationArchive);
+        if self.legacy_reward > 0 {
+            self.convert_legacy_reward(&archive.migration);
+        }
+        self.start_reward_epoch(archive.epoch + 1, archive.next_cutoff, reward_per_day);
+        Ok(())
+    }
+
+    fn start_reward_epoch(&mut self, epoch: u16, cutoff: i64, reward_per_day: u64) {
+        let legacy = self
+            .accrued_reward(cutoff, reward_per_day)
+            .checked_add(self.pending_reward)
+            .unwrap();
+        self.legacy_reward = self.legacy_reward.checked_add(legacy).unwrap();
         self.pending_reward = 0;
+        self.referral_bonus = 0;
+        if self.reward_time < cutoff {
+            self.reward_time = cutoff;
+        }
+        self.reward_epoch = epoch;
+    }
+
+    fn convert_legacy_reward(&mut self, migration: &RewardMintMigration) -> u64 {
+        let converted = (self.legacy_reward as u128 * migration.ratio_num as u128
+            / migration.ratio_den as u128) as u64;
+        self.pending_reward = self.pending_reward.checked_add(converted).unwrap();
+        self.legacy_reward = 0;
+        converted
+    }
+
+    /**
+     * Credit claimed reward as points of the given season
+     * season_points of the previous season is kept as last_season_points snapshot
+     */
+    pub fn add_points(&mut self, amount: u64, season: u16) {
+        if self.points_season != season {
+            self.last_season_points = match season.checked_sub(1) == Some(self.points_season) {
+                true => self.season_points,
+                false => 0,
+            };
+            self.season_points = 0;
+            self.points_season = season;
+        }
+        self.points = self.points.checked_add(amount).unwrap();
+        self.season_points = self.season_points.checked_add(amount).unwrap();
+    }
+
+    pub fn available_points(&self) -> u64 {
+        self.points.saturating_sub(self.points_spent)
+    }
+
+    /**
+     * Credit referral bonus to pending_reward up to the cap, returns the credited amount
+     */
+    pub fn credit_referral(&mut self, bonus: u64, cap: u64) -> u64 {
+        let credited = bonus.min(cap.saturating_sub(self.referral_earned));
+        self.pending_reward = self.pending_reward.checked_add(credited).unwrap();
+        self.referral_bonus = self.referral_bonus.checked_add(credited).unwrap();
+        self.referral_earned = self.referral_earned.checked_add(credited).unwrap();
+        credited
+    }
+
+    pub fn claim_reward(&mut self, now: i64, reward_per_day: u64) -> Result<u64> {
+        // Sum over all staked items, not only the last one
+        let reward = self
+            .accrued_reward(now, reward_per_day)
+            .checked_add(self.pending_reward)
+            .unwrap();
+        self.pending_reward = 0;
+        self.referral_bonus = 0;
         self.reward_time = now;
         Ok(reward)
     }
 }
+
+/**
+ * Layouts before account versioning and of version 1, only used by migrate_global_pool / migrate_user_pool
+ */
+#[derive(AnchorDeserialize)]
+pub struct GlobalPoolV0 {
+    pub admin: Pubkey,
+    pub reward_per_day: u64,
+    pub reward_mint: Pubkey,
+    pub reward_enable: bool,
+    pub total_staked_count: u64,
+    pub extra: u128,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct UserPoolV0 {
+    pub owner: Pubkey,
+    pub item_count: u64,
+    pub reward_time: i64,
+    pub pending_reward: u64,
+    pub extra: u128,
+    pub items: Vec<StakedNFT>,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct GlobalPoolV1 {
+    pub version: u8,
+    pub admin: Pubkey,
+    pub reward_per_day: u64,
+    pub reward_mint: Pubkey,
+    pub reward_enable: bool,
+    pub total_staked_count: u64,
+    pub pending_admin: Pubkey,
+    pub operator: Pubkey,
+    pub pauser: Pubkey,
+    pub treasurer: Pubkey,
+    pub pause_flags: u8,
+    pub config_delay: i64,
+    pub emergency_mode: bool,
+    pub reward_epoch: u16,
+    pub reward_liability: u64,
+    pub liability_time: i64,
+    pub migration: RewardMintMigration,
+    pub reward_decimals: u8,
+    pub max_reward_per_day: u64,
+    pub extra: u128,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct UserPoolV1 {
+    pub version: u8,
+    pub owner: Pubkey,
+    pub item_count: u64,
+    pub reward_time: i64,
+    pub pending_reward: u64,
+    pub reward_epoch: u16,
+    pub legacy_reward: u64,
+    pub extra: u128,
+    pub items: Vec<StakedNFT>,
+}
+
+/**
+ * spl-governance voter weight addin record, PDA of
+ * [VOTER_WEIGHT_SEED, realm, governing_token_mint, governing_token_owner]
+ * Layout and account discriminator must match spl-governance-addin-api
+ */
+#[account]
+#[derive(InitSpace)]
+pub struct VoterWeightRecord {
+    pub realm: Pubkey,
+    pub governing_token_mint: Pubkey,
+    pub governing_token_owner: Pubkey,
+    pub voter_weight: u64,
+    // Slot the weight is valid for, governance rejects it in any later slot
+    pub voter_weight_expiry: Option<u64>,
+    pub weight_action: Option<VoterWeightAction>,
+    pub weight_action_target: Option<Pubkey>,
+    pub reserved: [u8; 8],
+}
+
+impl VoterWeightRecord {
+    pub const DATA_SIZE: usize = 8 + VoterWeightRecord::INIT_SPACE;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VoterWeightAction {
+    CastVote,
+    CommentProposal,
+    CreateGovernance,
+    CreateProposal,
+    SignOffProposal,
+}
+
+/**
+ * Vote of a staked NFT on a proposal, PDA of [NFT_VOTE_RECORD_SEED, proposal, nft_mint]
+ * An NFT adds CastVote weight to a proposal only once, whichever wallet stakes it
+ */
+#[account]
+#[derive(InitSpace, Default)]
+pub struct NftVoteRecord {
+    pub version: u8,
+    pub proposal: Pubkey,
+    pub nft_mint: Pubkey,
+    pub governing_token_owner: Pubkey,
+}
+
+impl Versioned for NftVoteRecord {
+    const VERSION: u8 = 1;
+}
+
+impl NftVoteRecord {
+    pub const DATA_SIZE: usize = 8 + NftVoteRecord::INIT_SPACE;
+}
+
+/**
+ * Season archive, PDA of [SEASON_SEED, id]
+ * Aggregates are final once every user settled the season with settle_user_season
+ * Accrual is counted at reward_per_day of the season start, rate changes apply from the next season
+ */
+#[account]
+#[derive(InitSpace, Default)]
+pub struct Season {
+    pub version: u8,
+    pub id: u16,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub active: bool,
+    pub reward_per_day: u64,
+    pub total_reward: u64,
+    pub participants: u64,
+    pub extra: u128,
+}
+
+impl Versioned for Season {
+    const VERSION: u8 = 1;
+}
+
+impl Season {
+    pub const DATA_SIZE: usize = 8 + Season::INIT_SPACE;
+}
+
+/**
+ * Reward a user accrued during a season, PDA of [USER_SEASON_SEED, season, owner]
+ * Counted on claims and unlocks during the season, the rest is settled after its end by settle_user_season
+ * Leaderboards are built by reading all UserSeason accounts of a season
+ */
+#[account]
+#[derive(InitSpace, Default)]
+pub struct UserSeason {
+    pub version: u8,
+    pub season: u16,
+    pub owner: Pubkey,
+    pub reward: u64,
+}
+
+impl Versioned for UserSeason {
+    const VERSION: u8 = 1;
+}
+
+impl UserSeason {
+    pub const DATA_SIZE: usize = 8 + UserSeason::INIT_SPACE;
+}
+
+/**
+ * Verified creators of a staked NFT captured at lock, PDA of [STAKE_CREATORS_SEED, mint]
+ * Royalties accrue here from royalty_time while the NFT is staked, they are settled on unlock
+ * and creators pull them with claim_creator_royalty, so claims of the staker never need this account
+ * Closed to payer once the NFT is unlocked and every royalty is paid
+ */
+#[account]
+#[derive(InitSpace, Default)]
+pub struct StakeCreators {
+    pub version: u8,
+    pub mint: Pubkey,
+    pub payer: Pubkey,
+    pub staked: bool,
+    // Creator share captured at lock, the same share is kept out of the staker's reward
+    pub creator_share_bps: u16,
+    pub royalty_time: i64,
+    // Reward epoch of unpaid royalty, legacy royalty belongs to the previous one
+    pub reward_epoch: u16,
+    // Token metadata allows at most 5 creators
+    #[max_len(5)]
+    pub creators: Vec<CreatorRoyalty>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace, Default, Clone, Debug, PartialEq)]
+pub struct CreatorRoyalty {
+    pub address: Pubkey,
+    pub share: u8,
+    pub unpaid: u64,
+    // Royalty accrued before the last reward mint migration cutoff, converted once it's finalized
+    pub legacy: u64,
+}
+
+impl Versioned for StakeCreators {
+    const VERSION: u8 = 1;
+}
+
+impl StakeCreators {
+    pub const DATA_SIZE: usize = 8 + StakeCreators::INIT_SPACE;
+
+    /**
+     * Credit the royalty of the NFT reward accrued since royalty_time, nothing once it's unstaked
+     * Returns the credited amount
+     */
+    pub fn accrue(&mut self, until: i64, reward_per_day: u64) -> u64 {
+        if !self.staked || until <= self.royalty_time {
+            return 0;
+        }
+
+        let reward = ((until - self.royalty_time) as u128 * reward_per_day as u128 / DAY as u128) as u64;
+        self.royalty_time = until;
+        self.credit((reward as u128 * self.creator_share_bps as u128 / BPS_DENOMINATOR as u128) as u64)
+    }
+
+    /**
+     * Split royalty between creators by share, returns the credited amount
+     * Rounding leftover isn't credited and stays in the vault
+     */
+    pub fn credit(&mut self, royalty: u64) -> u64 {
+        let total_share: u64 = self.creators.iter().map(|creator| creator.share as u64).sum();
+        if total_share == 0 {
+            return 0;
+        }
+
+        let mut credited: u64 = 0;
+        for creator in self.creators.iter_mut() {
+            let amount = (royalty as u128 * creator.share as u128 / total_share as u128) as u64;
+            creator.unpaid = creator.unpaid.checked_add(amount).unwrap();
+            credited = credited.checked_add(amount).unwrap();
+        }
+        credited
+    }
+
+    /**
+     * Royalty owed to creators, including legacy royalty
+     */
+    pub fn unpaid(&self) -> u64 {
+        self.creators
+            .iter()
+            .fold(0u64, |total, creator| {
+                total.checked_add(creator.unpaid).unwrap().checked_add(creator.legacy).unwrap()
+            })
+    }
+
+    fn legacy(&self) -> u64 {
+        self.creators
+            .iter()
+            .fold(0u64, |total, creator| total.checked_add(creator.legacy).unwrap())
+    }
+
+    /**
+     * Move royalty accrued before the last reward mint migration cutoff into legacy
+     * and convert it to the new mint once the migration is finalized, like UserPool::settle_reward_epoch
+     * Records that skipped a whole migration must be settled from its archive by settle_archived_epoch first
+     */
+    pub fn settle_reward_epoch(&mut self, global_pool: &mut GlobalPool) -> Result<()> {
+        if self.reward_epoch < global_pool.reward_epoch {
+            require!(
+                self.reward_epoch + 1 == global_pool.reward_epoch && self.legacy() == 0,
+                StakingError::RewardEpochNotSettled
+            );
+            self.start_reward_epoch(
+                global_pool.reward_epoch,
+                global_pool.migration.cutoff,
+                global_pool.reward_per_day,
+            );
+        }
+
+        let migration = &mut global_pool.migration;
+        if !migration.active && self.legacy() > 0 {
+            let legacy = self.legacy();
+            let converted = self.convert_legacy_royalty(migration);
+            migration.legacy_outstanding = migration.legacy_outstanding.saturating_sub(legacy);
+            global_pool.reward_liability = global_pool.reward_liability.saturating_add(converted);
+        }
+        Ok(())
+    }
+
+    /**
+     * Settle the reward epoch of the given archive and move to the next one
+     */
+    pub fn settle_archived_epoch(&mut self, archive: &MigrationArchive, reward_per_day: u64) -> Result<()> {
+        require!(archive.epoch == self.reward_epoch, StakingError::InvalidMigrationArchive);
+        if self.legacy() > 0 {
+            self.convert_legacy_royalty(&archive.migration);
+        }
+        self.start_reward_epoch(archive.epoch + 1, archive.next_cutoff, reward_per_day);
+        Ok(())
+    }
+
+    fn start_reward_epoch(&mut self, epoch: u16, cutoff: i64, reward_per_day: u64) {
+        self.accrue(cutoff, reward_per_day);
+        for creator in self.creators.iter_mut() {
+            creator.legacy = creator.legacy.checked_add(creator.unpaid).unwrap();
+            creator.unpaid = 0;
+        }
+        if self.royalty_time < cutoff {
+            self.royalty_time = cutoff;
+        }
+        self.reward_epoch = epoch;
+    }
+
+    fn convert_legacy_royalty(&mut self, migration: &RewardMintMigration) -> u64 {
+        let mut converted: u64 = 0;
+        for creator in self.creators.iter_mut() {
+            let amount = (creator.legacy as u128 * migration.ratio_num as u128
+                / migration.ratio_den as u128) as u64;
+            creator.unpaid = creator.unpaid.checked_add(amount).unwrap();
+            creator.legacy = 0;
+            converted = converted.checked_add(amount).unwrap();
+        }
+        converted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    fn serialized_len<T: AccountSerialize>(account: &T) -> usize {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data.len()
+    }
+
+    fn user_pool_with(count: u64) -> UserPool {
+        let mut user_pool = UserPool::default();
+        for _ in 0..count {
+            user_pool
+                .add_nft(StakedNFT {
+                    nft_addr: Pubkey::new_unique(),
+                    stake_time: 0,
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+        user_pool
+    }
+
+    #[test]
+    fn global_pool_size_matches_serialized() {
+        assert_eq!(serialized_len(&GlobalPool::default()), GlobalPool::DATA_SIZE);
+    }
+
+    #[test]
+    fn config_change_size_matches_serialized() {
+        let config_change = ConfigChange {
+            new_reward_enable: Some(true),
+            new_reward_per_day: Some(1),
+            new_config_delay: Some(DAY),
+            new_fee_config: Some(FeeConfig {
+                treasury: Pubkey::new_unique(),
+                claim_fee_bps: MAX_CLAIM_FEE_BPS,
+                lock_fee: MAX_LOCK_FEE,
+            }),
+            new_creator_share_bps: Some(MAX_CREATOR_SHARE_BPS),
+            new_migration: Some(MigrationConfig {
+                new_mint: Pubkey::new_unique(),
+                claim_window: MIN_MIGRATION_CLAIM_WINDOW,
+                ratio_num: 1,
+                ratio_den: 1,
+            }),
+            ..Default::default()
+        };
+        assert_eq!(serialized_len(&config_change), ConfigChange::DATA_SIZE);
+        assert_eq!(serialized_len(&MigrationArchive::default()), MigrationArchive::DATA_SIZE);
+    }
+
+    #[test]
+    fn migration_ratio_and_window_are_bounded() {
+        let migration = MigrationConfig {
+            new_mint: Pubkey::new_unique(),
+            claim_window: MIN_MIGRATION_CLAIM_WINDOW,
+            ratio_num: MAX_MIGRATION_RATIO,
+            ratio_den: 1,
+        };
+        assert!(migration.is_valid());
+        assert!(!MigrationConfig { ratio_num: MAX_MIGRATION_RATIO + 1, ..migration }.is_valid());
+        assert!(!MigrationConfig { ratio_num: 1, ratio_den: MAX_MIGRATION_RATIO + 1, ..migration }.is_valid());
+        assert!(!MigrationConfig { ratio_den: 0, ..migration }.is_valid());
+        assert!(!MigrationConfig { claim_window: 0, ..migration }.is_valid());
+    }
+
+    #[test]
+    fn skipped_migrations_settle_from_archives() {
+        let mut user_pool = user_pool_with(1);
+        let mut global_pool = GlobalPool {
+            reward_per_day: 100,
+            reward_epoch: 2,
+            migration: RewardMintMigration {
+                cutoff: 3 * DAY,
+                ratio_num: 1,
+                ratio_den: 2,
+                active: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // Each legacy balance converts with the ratio of its own migration
+        assert!(user_pool.settle_reward_epoch(&mut global_pool).is_err());
+
+        let archives = [
+            MigrationArchive {
+                epoch: 0,
+                next_cutoff: DAY,
+                ..Default::default()
+            },
+            MigrationArchive {
+                epoch: 1,
+                migration: RewardMintMigration {
+                    cutoff: DAY,
+                    ratio_num: 2,
+                    ratio_den: 1,
+                    ..Default::default()
+                },
+                next_cutoff: 3 * DAY,
+                ..Default::default()
+            },
+        ];
+        assert!(user_pool.settle_archived_epoch(&archives[1], 100).is_err());
+        user_pool.settle_archived_epoch(&archives[0], 100).unwrap();
+        assert_eq!((user_pool.legacy_reward, user_pool.reward_epoch), (100, 1));
+        user_pool.settle_archived_epoch(&archives[1], 100).unwrap();
+        assert_eq!((user_pool.legacy_reward, user_pool.reward_epoch), (200 + 200, 2));
+
+        user_pool.settle_reward_epoch(&mut global_pool).unwrap();
+        assert_eq!(user_pool.legacy_reward, 400);
+        global_pool.migration.active = false;
+        user_pool.settle_reward_epoch(&mut global_pool).unwrap();
+        assert_eq!((user_pool.legacy_reward, user_pool.pending_reward), (0, 200));
+        assert_eq!(user_pool.reward_time, 3 * DAY);
+    }
+
+    #[test]
+    fn user_pool_is_exactly_full_at_n_items() {
+        for count in 0..=DEFAULT_STAKE_SIZE as u64 * 2 {
+            let user_pool = user_pool_with(count);
+            let data_len = UserPool::size_calc(count);
+            assert_eq!(serialized_len(&user_pool), data_len);
+            assert!(user_pool.is_full(data_len));
+            assert!(!user_pool.is_full(UserPool::size_calc(count + 1)));
+        }
+    }
+
+    #[test]
+    fn claim_reward_sums_all_items() {
+        let mut user_pool = user_pool_with(3);
+        user_pool.pending_reward = 5;
+        assert_eq!(user_pool.claim_reward(DAY, 100).unwrap(), 305);
+        assert_eq!(user_pool.pending_reward, 0);
+        assert_eq!(user_pool.reward_time, DAY);
+        assert_eq!(user_pool.accrued_reward(DAY, 100), 0);
+    }
+
+    #[test]
+    fn creator_royalty_is_split_by_share() {
+        let item = StakedNFT {
+            creator_share_bps: 1_000,
+            ..Default::default()
+        };
+        let royalty = item.royalty(1_000);
+        assert_eq!(royalty, 100);
+
+        let mut stake_creators = StakeCreators {
+            creators: vec![
+                CreatorRoyalty { address: Pubkey::new_unique(), share: 70, unpaid: 0, legacy: 0 },
+                CreatorRoyalty { address: Pubkey::new_unique(), share: 30, unpaid: 5, legacy: 0 },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(stake_creators.credit(royalty), 100);
+        // Rounding leftover isn't credited
+        assert_eq!(stake_creators.credit(3), 2);
+        assert_eq!(stake_creators.creators[0].unpaid, 70 + 2);
+        assert_eq!(stake_creators.creators[1].unpaid, 5 + 30);
+        assert_eq!(stake_creators.unpaid(), 107);
+
+        // No verified creators, nothing is credited
+        assert_eq!(StakeCreators::default().credit(royalty), 0);
+    }
+
+    #[test]
+    fn royalty_is_kept_out_of_staker_reward() {
+        let mut user_pool = user_pool_with(2);
+        user_pool.items[0].creator_share_bps = 1_000;
+        assert_eq!(user_pool.accrued_reward(DAY, 100), 90 + 100);
+        assert_eq!(user_pool.accrued_royalty(DAY, 100), 10);
+
+        // Creators accrue the same royalty from their own checkpoint, whenever the staker claims
+        let mut stake_creators = StakeCreators {
+            staked: true,
+            creator_share_bps: 1_000,
+            creators: vec![CreatorRoyalty { address: Pubkey::new_unique(), share: 100, unpaid: 0, legacy: 0 }],
+            ..Default::default()
+        };
+        assert_eq!(user_pool.claim_reward(DAY / 2, 100).unwrap(), 45 + 50);
+        assert_eq!(user_pool.remove_nft(user_pool.items[0].nft_addr, DAY, 100).unwrap(), 45);
+        assert_eq!(stake_creators.accrue(DAY, 100), 10);
+        assert_eq!(stake_creators.royalty_time, DAY);
+
+        // Nothing accrues once unstaked
+        stake_creators.staked = false;
+        assert_eq!(stake_creators.accrue(2 * DAY, 100), 0);
+        assert_eq!(stake_creators.unpaid(), 10);
+    }
+
+    #[test]
+    fn royalty_converts_with_its_migration() {
+        let mut stake_creators = StakeCreators {
+            staked: true,
+            creator_share_bps: 1_000,
+            creators: vec![CreatorRoyalty { address: Pubkey::new_unique(), share: 100, unpaid: 0, legacy: 0 }],
+            ..Default::default()
+        };
+        let mut global_pool = GlobalPool {
+            reward_per_day: 100,
+            reward_epoch: 2,
+            migration: RewardMintMigration {
+                cutoff: DAY,
+                ratio_num: 1,
+                ratio_den: 2,
+                legacy_outstanding: 1_000,
+                active: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert!(stake_creators.settle_reward_epoch(&mut global_pool).is_err());
+
+        // Royalty accrued before the cutoff stays in the legacy mint until the migration is finalized
+        global_pool.reward_epoch = 1;
+        stake_creators.settle_reward_epoch(&mut global_pool).unwrap();
+        assert_eq!((stake_creators.creators[0].legacy, stake_creators.reward_epoch), (10, 1));
+        assert_eq!(stake_creators.royalty_time, DAY);
+        assert_eq!(stake_creators.accrue(2 * DAY, 100), 10);
+
+        global_pool.migration.active = false;
+        stake_creators.settle_reward_epoch(&mut global_pool).unwrap();
+        assert_eq!((stake_creators.creators[0].legacy, stake_creators.creators[0].unpaid), (0, 5 + 10));
+        assert_eq!(global_pool.migration.legacy_outstanding, 990);
+        assert_eq!(global_pool.reward_liability, 5);
+    }
+
+    #[test]
+    fn stake_creators_size_fits_max_creators() {
+        let stake_creators = StakeCreators {
+            creators: vec![CreatorRoyalty::default(); 5],
+            ..Default::default()
+        };
+        assert_eq!(serialized_len(&stake_creators), StakeCreators::DATA_SIZE);
+    }
+
+    #[test]
+    fn claim_fee_rounds_down() {
+        let global_pool = GlobalPool {
+            claim_fee_bps: 250,
+            ..Default::default()
+        };
+        assert_eq!(global_pool.claim_fee(1_000), 25);
+        assert_eq!(global_pool.claim_fee(39), 0);
+        assert_eq!(global_pool.claim_fee(u64::MAX), u64::MAX / 40);
+    }
+
+    #[test]
+    fn referral_bonus_is_capped_and_not_compounded() {
+        let global_pool = GlobalPool {
+            referral_bps: 500,
+            ..Default::default()
+        };
+        assert_eq!(global_pool.referral_bonus(1_000), 50);
+
+        let mut referrer = user_pool_with(1);
+        assert_eq!(referrer.credit_referral(50, 80), 50);
+        assert_eq!(referrer.credit_referral(50, 80), 30);
+        assert_eq!(referrer.credit_referral(50, 80), 0);
+        assert_eq!((referrer.pending_reward, referrer.referral_bonus, referrer.referral_earned), (80, 80, 80));
+
+        // Claim pays the bonus out and only staking reward earns the referrer's own referrer a bonus
+        let reward = referrer.claim_reward(DAY, 100).unwrap();
+        assert_eq!(reward, 180);
+        assert_eq!(referrer.referral_bonus, 0);
+        assert_eq!(referrer.referral_earned, 80);
+    }
+
+    #[test]
+    fn voter_weight_record_matches_addin_layout() {
+        // sha256("account:VoterWeightRecord")[..8] as expected by spl-governance
+        assert_eq!(VoterWeightRecord::DISCRIMINATOR, [46, 249, 155, 75, 153, 248, 116, 9]);
+        assert_eq!(VoterWeightRecord::DATA_SIZE, 8 + 32 * 3 + 8 + 9 + 2 + 33 + 8);
+        assert_eq!(serialized_len(&NftVoteRecord::default()), NftVoteRecord::DATA_SIZE);
+    }
+
+    #[test]
+    fn voter_weight_counts_duration() {
+        let mut user_pool = user_pool_with(3);
+        user_pool.items[1].stake_time = VOTER_WEIGHT_MAX_DURATION;
+        user_pool.items[2].stake_time = VOTER_WEIGHT_MAX_DURATION * 2;
+
+        // Just staked NFT votes with the base weight
+        let now = VOTER_WEIGHT_MAX_DURATION * 2;
+        assert_eq!(user_pool.voter_weight(now), VOTER_WEIGHT_PER_NFT * 2 * 2 + VOTER_WEIGHT_PER_NFT);
+
+        // Half of the max duration gives half of the bonus
+        let now = VOTER_WEIGHT_MAX_DURATION * 5 / 2;
+        assert_eq!(user_pool.voter_weight(now), VOTER_WEIGHT_PER_NFT * 2 * 2 + VOTER_WEIGHT_PER_NFT * 3 / 2);
+    }
+
+    #[test]
+    fn points_roll_over_seasons() {
+        let mut user_pool = UserPool::default();
+        user_pool.add_points(10, 0);
+        user_pool.add_points(5, 0);
+        assert_eq!((user_pool.points, user_pool.season_points), (15, 15));
+
+        user_pool.add_points(7, 1);
+        assert_eq!((user_pool.season_points, user_pool.last_season_points), (7, 15));
+
+        // Skipped season snapshots nothing
+        user_pool.add_points(1, 3);
+        assert_eq!((user_pool.season_points, user_pool.last_season_points), (1, 0));
+        assert_eq!(user_pool.points, 23);
+
+        user_pool.points_spent = 20;
+        assert_eq!(user_pool.available_points(), 3);
+    }
+
+    #[test]
+    fn season_reward_counts_from_checkpoint() {
+        let mut user_pool = user_pool_with(2);
+        user_pool.items[1].stake_time = 3 * DAY;
+        user_pool.reward_time = DAY;
+
+        // Claims don't matter, everything since the checkpoint counts
+        assert_eq!(user_pool.season_reward(0, 4 * DAY, 10), 40 + 10);
+        // Items staked after the checkpoint count from their stake time
+        assert_eq!(user_pool.season_reward(2 * DAY, 4 * DAY, 10), 20 + 10);
+        assert_eq!(user_pool.season_reward(5 * DAY, 4 * DAY, 10), 0);
+    }
+
+    #[test]
+    fn season_sizes_match_serialized() {
+        assert_eq!(serialized_len(&Season::default()), Season::DATA_SIZE);
+        assert_eq!(serialized_len(&UserSeason::default()), UserSeason::DATA_SIZE);
+    }
+
+    #[test]
+    fn user_pool_growth_ignores_vec_capacity() {
+        let mut user_pool = user_pool_with(1);
+        user_pool.items.reserve(DEFAULT_STAKE_SIZE * 4);
+        assert!(user_pool.is_full(UserPool::size_calc(1)));
+
+        let data = {
+            let mut data = Vec::new();
+            user_pool.try_serialize(&mut data).unwrap();
+            data.resize(UserPool::size_calc(1 + DEFAULT_STAKE_SIZE as u64), 0);
+            data
+        };
+        let loaded = UserPool::try_deserialize(&mut &data[..]).unwrap();
+        assert!(!loaded.is_full(data.len()));
+    }
+
+    #[test]
+    fn current_rejects_older_versions() {
+        let mut data = Vec::new();
+        user_pool_with(1).try_serialize(&mut data).unwrap();
+        assert_eq!(data[8], UserPool::VERSION);
+        assert!(Current::<UserPool>::try_deserialize(&mut &data[..]).is_ok());
+
+        // Unversioned pools have the first byte of the owner there
+        for version in [0, 1, 0xff] {
+            data[8] = version;
+            let err = Current::<UserPool>::try_deserialize(&mut &data[..]).err().unwrap();
+            assert_eq!(err, error!(StakingError::InvalidAccountVersion));
+        }
+    }
+}
@@ -1,7 +1,34 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{program::invoke, system_instruction::transfer};
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::{invoke, invoke_signed},
+    system_instruction::{allocate, assign, create_account, transfer},
+    sysvar::instructions as sysvar_instructions,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address_with_program_id},
+    token::{Mint, Token, TokenAccount},
+    token_2022::{
+        self,
+        spl_token_2022::{
+            self,
+            extension::{metadata_pointer, ExtensionType},
+            instruction::AuthorityType,
+        },
+        Token2022,
+    },
+};
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata, TokenRecord},
+    instructions::{RevokeStakingV1CpiBuilder, UnlockV1CpiBuilder},
+};
+use spl_token_metadata_interface::state::{Field, TokenMetadata};
 use std::cmp::Ordering;
 
+use crate::constant::*;
+use crate::error::*;
+use crate::state::*;
+
 pub fn resize_account<'info>(
     account_info: AccountInfo<'info>,
     new_space: usize,
@@ -33,3 +60,632 @@ pub fn resize_account<'info>(
     account_info.realloc(new_space, false)?;
     Ok(())
 }
+
+/**
+ * Shrink user pool PDA and refund rent if more than DEFAULT_STAKE_SIZE free items are left
+ */
+pub fn shrink_user_pool<'info>(
+    user_pool: AccountInfo<'info>,
+    item_count: u64,
+    refund_to: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+) -> Result<()> {
+    let shrink_size = UserPool::size_calc(item_count + DEFAULT_STAKE_SIZE as u64);
+    if user_pool.data_len() > shrink_size {
+        resize_account(user_pool, shrink_size, refund_to, system_program)?;
+    }
+    Ok(())
+}
+
+/**
+ * Create a program owned PDA, topping up lamports sent to its address beforehand instead of failing
+ */
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = Rent::get()?.minimum_balance(space);
+    create_owned_pda_account(payer, account_info, space, lamports, &crate::ID, seeds, system_program)
+}
+
+/**
+ * Create a PDA owned by owner with at least lamports, for accounts that grow after creation
+ * lamports sent to its address beforehand are topped up instead of failing
+ */
+pub fn create_owned_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account_info: &AccountInfo<'info>,
+    space: usize,
+    lamports: u64,
+    owner: &Pubkey,
+    seeds: &[&[u8]],
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let accounts = [payer.clone(), account_info.clone(), system_program.clone()];
+    if account_info.lamports() == 0 {
+        return Ok(invoke_signed(
+            &create_account(&payer.key(), &account_info.key(), lamports, space as u64, owner),
+            &accounts,
+            &[seeds],
+        )?);
+    }
+
+    let top_up = lamports.saturating_sub(account_info.lamports());
+    if top_up > 0 {
+        invoke(&transfer(&payer.key(), &account_info.key(), top_up), &accounts)?;
+    }
+    invoke_signed(&allocate(&account_info.key(), space as u64), &accounts, &[seeds])?;
+    invoke_signed(&assign(&account_info.key(), owner), &accounts, &[seeds])?;
+    Ok(())
+}
+
+/**
+ * Close a program owned account and send its rent to destination
+ */
+pub fn close_account(account_info: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(account_info.lamports())
+        .unwrap();
+    **account_info.try_borrow_mut_lamports()? = 0;
+    account_info.assign(&anchor_lang::system_program::ID);
+    account_info.realloc(0, false)?;
+    Ok(())
+}
+
+/**
+ * Notify the hook program set on global pool, nothing to do if unset
+ * remaining_accounts[0] is the hook program, remaining_accounts[1] the hook authority PDA
+ * and the rest are passed through to it
+ * hook authority signs the CPI so the hook can check the caller, it owns nothing so the hook can't
+ * reuse the signature against the vault or staked NFTs. A hook failure reverts the action
+ */
+pub fn invoke_stake_hook<'info>(
+    global_pool: &GlobalPool,
+    remaining_accounts: &[AccountInfo<'info>],
+    args: StakeHookArgs,
+) -> Result<()> {
+    if global_pool.hook_program.eq(&Pubkey::default()) {
+        return Ok(());
+    }
+
+    let [hook_program, hook_authority, hook_accounts @ ..] = remaining_accounts else {
+        return err!(StakingError::InvalidHookProgram);
+    };
+    require!(
+        hook_program.key().eq(&global_pool.hook_program) && hook_program.executable,
+        StakingError::InvalidHookProgram
+    );
+    let (authority, authority_bump) =
+        Pubkey::find_program_address(&[HOOK_AUTHORITY_SEED.as_bytes()], &crate::ID);
+    require!(hook_authority.key().eq(&authority), StakingError::InvalidHookAuthority);
+
+    let mut data = STAKE_HOOK_DISCRIMINATOR.to_vec();
+    args.serialize(&mut data)?;
+
+    let mut accounts = vec![AccountMeta::new_readonly(authority, true)];
+    let mut account_infos = vec![hook_authority.clone()];
+    for account in hook_accounts {
+        accounts.push(match account.is_writable {
+            true => AccountMeta::new(account.key(), account.is_signer),
+            false => AccountMeta::new_readonly(account.key(), account.is_signer),
+        });
+        account_infos.push(account.clone());
+    }
+    account_infos.push(hook_program.clone());
+
+    invoke_signed(
+        &Instruction {
+            program_id: hook_program.key(),
+            accounts,
+            data,
+        },
+        &account_infos,
+        &[&[HOOK_AUTHORITY_SEED.as_bytes(), &[authority_bump]]],
+    )?;
+    Ok(())
+}
+
+/**
+ * Count reward accrued during the active season since the user's last checkpoint into its UserSeason
+ * Called before staked items change, seasons ended since the last checkpoint must be settled first
+ * Season accounts are only required while a season is active
+ */
+pub fn checkpoint_season(
+    global_pool: &GlobalPool,
+    user_pool: &mut UserPool,
+    season: Option<&mut Season>,
+    user_season: Option<&mut UserSeason>,
+    now: i64,
+) -> Result<()> {
+    require!(user_pool.season == global_pool.points_season, StakingError::SeasonNotSettled);
+    if !global_pool.season_active {
+        return Ok(());
+    }
+
+    let (Some(season), Some(user_season)) = (season, user_season) else {
+        return err!(StakingError::MissingSeasonAccounts);
+    };
+    if user_season.version == 0 {
+        user_season.version = UserSeason::VERSION;
+        user_season.season = season.id;
+        user_season.owner = user_pool.owner;
+        season.participants += 1;
+    }
+
+    let from = season.start_time.max(user_pool.season_time);
+    let season_reward = user_pool.season_reward(from, now, season.reward_per_day);
+    user_season.reward = user_season.reward.checked_add(season_reward).unwrap();
+    season.total_reward = season.total_reward.checked_add(season_reward).unwrap();
+    user_pool.season_time = now;
+    Ok(())
+}
+
+/**
+ * Transfer the flat lock fee from user to the treasury, nothing to do if it's zero
+ * Returns the collected lamports
+ */
+pub fn collect_lock_fee<'info>(
+    global_pool: &GlobalPool,
+    user: AccountInfo<'info>,
+    treasury: Option<AccountInfo<'info>>,
+    system_program: AccountInfo<'info>,
+) -> Result<u64> {
+    if global_pool.lock_fee == 0 {
+        return Ok(0);
+    }
+
+    let treasury = treasury.ok_or(error!(StakingError::InvalidTreasury))?;
+    require!(treasury.key().eq(&global_pool.treasury), StakingError::InvalidTreasury);
+
+    invoke(
+        &transfer(&user.key(), &treasury.key(), global_pool.lock_fee),
+        &[user, treasury, system_program],
+    )?;
+    Ok(global_pool.lock_fee)
+}
+
+/**
+ * Credit creators' royalty accrued by a staked NFT to its StakeCreators account, nothing to do if it doesn't exist
+ * None settles nothing, as emergency unlock forfeits the reward since the last claim
+ * otherwise its reward epoch is settled first, so royalty accrued before a migration cutoff stays legacy
+ * unstaked marks the NFT unlocked, so the account can be closed once every royalty is paid
+ * it's closed right away if nothing is owed and its payer is passed
+ * Returns the credited amount
+ */
+pub fn settle_stake_creators<'info>(
+    global_pool: &mut GlobalPool,
+    stake_creators: &AccountInfo<'info>,
+    until: Option<i64>,
+    unstaked: Option<&AccountInfo<'info>>,
+) -> Result<u64> {
+    if stake_creators.data_is_empty() {
+        return Ok(0);
+    }
+
+    require!(stake_creators.owner.eq(&crate::ID), StakingError::InvalidStakeCreators);
+    let mut record = StakeCreators::try_deserialize(&mut &stake_creators.data.borrow()[..])?;
+    let credited = match until {
+        Some(until) => {
+            record.settle_reward_epoch(global_pool)?;
+            record.accrue(until, global_pool.reward_per_day)
+        }
+        None => 0,
+    };
+    if let Some(payer) = unstaked {
+        record.staked = false;
+        if record.unpaid() == 0 && payer.key().eq(&record.payer) {
+            close_account(stake_creators, payer)?;
+            return Ok(credited);
+        }
+    }
+    record.try_serialize(&mut &mut stake_creators.data.borrow_mut()[..])?;
+    Ok(credited)
+}
+
+/**
+ * Staked pNFT accounts shared by unlock_pnft, force_unlock and emergency_unlock
+ */
+#[derive(Accounts)]
+pub struct UnlockNftAccounts<'info> {
+    pub token_mint: Box<Account<'info, Mint>>,
+    //  Holder is checked against the user pool owner in unlock
+    #[account(
+        mut,
+        token::mint = token_mint,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+    /// CHECK: address is derived from token_mint
+    #[account(
+        address = MasterEdition::find_pda(&token_mint.key()).0 @ StakingError::InvalidEdition,
+    )]
+    pub token_mint_edition: AccountInfo<'info>,
+    /// CHECK: address is derived from token_mint and token_account
+    #[account(
+        mut,
+        address = TokenRecord::find_pda(&token_mint.key(), &token_account.key()).0 @ StakingError::InvalidTokenRecord,
+    )]
+    pub token_mint_record: AccountInfo<'info>,
+    /// CHECK: address is derived from token_mint and owned by token metadata program
+    #[account(
+        mut,
+        address = Metadata::find_pda(&token_mint.key()).0 @ StakingError::InvalidMetadata,
+        owner = mpl_token_metadata::ID @ StakingError::InvalidMetadataOwner,
+    )]
+    pub mint_metadata: UncheckedAccount<'info>,
+    /// CHECK: instruction will fail if wrong rules are supplied
+    pub auth_rules: UncheckedAccount<'info>,
+    /// CHECK: address is checked against sysvar instructions id
+    #[account(address = sysvar_instructions::ID @ StakingError::InvalidSysvarInstructions)]
+    pub sysvar_instructions: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    /// CHECK: address is checked against token metadata program id
+    #[account(address = mpl_token_metadata::ID @ StakingError::InvalidTokenMetadataProgram)]
+    pub token_metadata_program: AccountInfo<'info>,
+    /// CHECK: address is checked against auth rules program id
+    #[account(address = AUTH_RULES_PROGRAM_ID @ StakingError::InvalidAuthRulesProgram)]
+    pub auth_rules_program: AccountInfo<'info>,
+
+    //  Staking receipt is burned if one was minted at lock
+    /// CHECK: receipt mint PDA of token_mint, may not exist
+    #[account(
+        mut,
+        seeds = [RECEIPT_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub receipt_mint: UncheckedAccount<'info>,
+    /// CHECK: owner's ATA of receipt_mint, checked when burning the receipt
+    #[account(mut)]
+    pub receipt_account: Option<UncheckedAccount<'info>>,
+    pub token_2022_program: Option<Program<'info, Token2022>>,
+
+    //  Creators' royalty of the settled reward is credited if the NFT has stake creators
+    /// CHECK: stake creators PDA of token_mint, may not exist
+    #[account(
+        mut,
+        seeds = [STAKE_CREATORS_SEED.as_ref(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub stake_creators: UncheckedAccount<'info>,
+}
+
+impl<'info> UnlockNftAccounts<'info> {
+    /**
+     * Unlock the pNFT in owner's token account and revoke the staking delegate of global authority
+     * revoke_authority is the owner, or global authority revoking its own delegate so owner signature isn't needed
+     */
+    pub fn unlock(
+        &self,
+        global_pool: &AccountInfo<'info>,
+        global_bump: u8,
+        owner: &AccountInfo<'info>,
+        revoke_authority: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        require!(self.token_account.owner.eq(owner.key), StakingError::InvalidTokenAccount);
+
+        let seeds = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[global_bump]];
+        let delegate_seeds = &[&seeds[..]];
+
+        UnlockV1CpiBuilder::new(&self.token_metadata_program)
+            .authority(global_pool)
+            .token_owner(Some(owner))
+            .token(&self.token_account.to_account_info())
+            .mint(&self.token_mint.to_account_info())
+            .metadata(&self.mint_metadata)
+            .edition(Some(self.token_mint_edition.as_ref()))
+            .token_record(Some(self.token_mint_record.as_ref()))
+            .payer(payer)
+            .system_program(system_program)
+            .sysvar_instructions(&self.sysvar_instructions)
+            .spl_token_program(Some(&self.token_program.to_account_info()))
+            .authorization_rules_program(Some(self.auth_rules_program.as_ref()))
+            .authorization_rules(Some(self.auth_rules.as_ref()))
+            .invoke_signed(delegate_seeds)?;
+
+        RevokeStakingV1CpiBuilder::new(&self.token_metadata_program)
+            .delegate(global_pool)
+            .metadata(&self.mint_metadata.to_account_info())
+            .master_edition(Some(self.token_mint_edition.as_ref()))
+            .token_record(Some(self.token_mint_record.as_ref()))
+            .mint(&self.token_mint.to_account_info())
+            .token(&self.token_account.to_account_info())
+            .authority(revoke_authority)
+            .payer(payer)
+            .system_program(system_program)
+            .sysvar_instructions(&self.sysvar_instructions)
+            .spl_token_program(Some(&self.token_program.to_account_info()))
+            .authorization_rules_program(Some(self.auth_rules_program.as_ref()))
+            .authorization_rules(Some(self.auth_rules.as_ref()))
+            .invoke_signed(delegate_seeds)?;
+
+        Ok(())
+    }
+
+    /**
+     * Burn the staking receipt if one was minted at lock, see ReceiptAccounts::burn_receipt
+     */
+    pub fn burn_receipt_if_any(
+        &self,
+        global_pool: AccountInfo<'info>,
+        global_bump: u8,
+        receipt_bump: u8,
+        owner: AccountInfo<'info>,
+    ) -> Result<()> {
+        if self.receipt_mint.data_is_empty() {
+            return Ok(());
+        }
+
+        ReceiptAccounts {
+            global_pool,
+            global_bump,
+            receipt_mint: self.receipt_mint.to_account_info(),
+            receipt_bump,
+            receipt_account: self
+                .receipt_account
+                .as_ref()
+                .ok_or(error!(StakingError::InvalidReceiptAccounts))?
+                .to_account_info(),
+            owner,
+            token_2022_program: self
+                .token_2022_program
+                .as_ref()
+                .ok_or(error!(StakingError::InvalidReceiptAccounts))?
+                .to_account_info(),
+        }
+        .burn_receipt()
+    }
+}
+
+/**
+ * Accounts of the staking receipt of one NFT
+ * receipt_mint is the PDA of [RECEIPT_SEED, nft mint], receipt_account the owner's token-2022 ATA of it
+ */
+pub struct ReceiptAccounts<'info> {
+    pub global_pool: AccountInfo<'info>,
+    pub global_bump: u8,
+    pub receipt_mint: AccountInfo<'info>,
+    pub receipt_bump: u8,
+    pub receipt_account: AccountInfo<'info>,
+    pub owner: AccountInfo<'info>,
+    pub token_2022_program: AccountInfo<'info>,
+}
+
+impl<'info> ReceiptAccounts<'info> {
+    fn check_receipt_account(&self) -> Result<()> {
+        require!(
+            self.receipt_account.key().eq(&get_associated_token_address_with_program_id(
+                &self.owner.key(),
+                &self.receipt_mint.key(),
+                &spl_token_2022::ID,
+            )),
+            StakingError::InvalidReceiptAccounts
+        );
+        Ok(())
+    }
+
+    /**
+     * Create the non-transferable receipt mint and mint one to the owner, who pays the rent
+     * Token metadata lives in the mint and points to the staked NFT with RECEIPT_ORIGINAL_MINT_FIELD
+     * global pool is permanent delegate and close authority, so any unlock path can burn it
+     */
+    pub fn mint_receipt(
+        &self,
+        nft_metadata: &Metadata,
+        associated_token_program: AccountInfo<'info>,
+        system_program: AccountInfo<'info>,
+    ) -> Result<()> {
+        self.check_receipt_account()?;
+
+        let global_key = self.global_pool.key();
+        let receipt_key = self.receipt_mint.key();
+        let token_2022_key = self.token_2022_program.key();
+        let global_seeds: &[&[u8]] = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[self.global_bump]];
+        let receipt_seeds: &[&[u8]] = &[
+            RECEIPT_SEED.as_bytes(),
+            nft_metadata.mint.as_ref(),
+            &[self.receipt_bump],
+        ];
+
+        let metadata = TokenMetadata {
+            mint: receipt_key,
+            name: nft_metadata.name.trim_end_matches('\0').to_string(),
+            symbol: nft_metadata.symbol.trim_end_matches('\0').to_string(),
+            uri: nft_metadata.uri.trim_end_matches('\0').to_string(),
+            additional_metadata: vec![(
+                RECEIPT_ORIGINAL_MINT_FIELD.to_string(),
+                nft_metadata.mint.to_string(),
+            )],
+            ..Default::default()
+        };
+
+        // Token metadata is reallocated into the mint by token-2022, so fund it upfront
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+            ExtensionType::NonTransferable,
+            ExtensionType::MetadataPointer,
+            ExtensionType::MintCloseAuthority,
+            ExtensionType::PermanentDelegate,
+        ])?;
+        let lamports = Rent::get()?.minimum_balance(space + metadata.tlv_size_of()?);
+        create_owned_pda_account(
+            &self.owner,
+            &self.receipt_mint,
+            space,
+            lamports,
+            &token_2022_key,
+            receipt_seeds,
+            &system_program,
+        )?;
+
+        let mint_accounts = [self.receipt_mint.clone(), self.token_2022_program.clone()];
+        invoke(
+            &spl_token_2022::instruction::initialize_non_transferable_mint(&token_2022_key, &receipt_key)?,
+            &mint_accounts,
+        )?;
+        invoke(
+            &metadata_pointer::instruction::initialize(
+                &token_2022_key,
+                &receipt_key,
+                Some(global_key),
+                Some(receipt_key),
+            )?,
+            &mint_accounts,
+        )?;
+        invoke(
+            &spl_token_2022::instruction::initialize_mint_close_authority(
+                &token_2022_key,
+                &receipt_key,
+                Some(&global_key),
+            )?,
+            &mint_accounts,
+        )?;
+        invoke(
+            &spl_token_2022::instruction::initialize_permanent_delegate(
+                &token_2022_key,
+                &receipt_key,
+                &global_key,
+            )?,
+            &mint_accounts,
+        )?;
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                self.token_2022_program.clone(),
+                token_2022::InitializeMint2 {
+                    mint: self.receipt_mint.clone(),
+                },
+            ),
+            0,
+            &global_key,
+            None,
+        )?;
+
+        let metadata_accounts = [
+            self.receipt_mint.clone(),
+            self.global_pool.clone(),
+            self.token_2022_program.clone(),
+        ];
+        invoke_signed(
+            &spl_token_metadata_interface::instruction::initialize(
+                &token_2022_key,
+                &receipt_key,
+                &global_key,
+                &receipt_key,
+                &global_key,
+                metadata.name,
+                metadata.symbol,
+                metadata.uri,
+            ),
+            &metadata_accounts,
+            &[global_seeds],
+        )?;
+        for (key, value) in metadata.additional_metadata {
+            invoke_signed(
+                &spl_token_metadata_interface::instruction::update_field(
+                    &token_2022_key,
+                    &receipt_key,
+                    &global_key,
+                    Field::Key(key),
+                    value,
+                ),
+                &metadata_accounts,
+                &[global_seeds],
+            )?;
+        }
+
+        associated_token::create(CpiContext::new(
+            associated_token_program,
+            associated_token::Create {
+                payer: self.owner.clone(),
+                associated_token: self.receipt_account.clone(),
+                authority: self.owner.clone(),
+                mint: self.receipt_mint.clone(),
+                system_program,
+                token_program: self.token_2022_program.clone(),
+            },
+        ))?;
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                self.token_2022_program.clone(),
+                token_2022::MintTo {
+                    mint: self.receipt_mint.clone(),
+                    to: self.receipt_account.clone(),
+                    authority: self.global_pool.clone(),
+                },
+                &[global_seeds],
+            ),
+            1,
+        )?;
+
+        // Fixed supply of one
+        token_2022::set_authority(
+            CpiContext::new_with_signer(
+                self.token_2022_program.clone(),
+                token_2022::SetAuthority {
+                    current_authority: self.global_pool.clone(),
+                    account_or_mint: self.receipt_mint.clone(),
+                },
+                &[global_seeds],
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    /**
+     * Burn the receipt as permanent delegate and close its mint, rent goes back to the owner
+     * The owner's empty receipt account is closed too if the owner signed
+     */
+    pub fn burn_receipt(&self) -> Result<()> {
+        self.check_receipt_account()?;
+
+        let global_seeds: &[&[u8]] = &[GLOBAL_AUTHORITY_SEED.as_bytes(), &[self.global_bump]];
+
+        let receipt_account_exists = !self.receipt_account.data_is_empty();
+        if receipt_account_exists {
+            let amount = token_2022::accessor::amount(&self.receipt_account)?;
+            if amount > 0 {
+                token_2022::burn(
+                    CpiContext::new_with_signer(
+                        self.token_2022_program.clone(),
+                        token_2022::Burn {
+                            mint: self.receipt_mint.clone(),
+                            from: self.receipt_account.clone(),
+                            authority: self.global_pool.clone(),
+                        },
+                        &[global_seeds],
+                    ),
+                    amount,
+                )?;
+            }
+            if self.owner.is_signer {
+                token_2022::close_account(CpiContext::new(
+                    self.token_2022_program.clone(),
+                    token_2022::CloseAccount {
+                        account: self.receipt_account.clone(),
+                        destination: self.owner.clone(),
+                        authority: self.owner.clone(),
+                    },
+                ))?;
+            }
+        }
+
+        token_2022::close_account(CpiContext::new_with_signer(
+            self.token_2022_program.clone(),
+            token_2022::CloseAccount {
+                account: self.receipt_mint.clone(),
+                destination: self.owner.clone(),
+                authority: self.global_pool.clone(),
+            },
+            &[global_seeds],
+        ))?;
+
+        Ok(())
+    }
+}
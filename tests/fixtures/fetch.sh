@@ -0,0 +1,6 @@
+#!/bin/sh
+# Dumps the mainnet programs loaded at genesis by anchor test, see Anchor.toml
+set -e
+cd "$(dirname "$0")"
+solana program dump -u m metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s mpl_token_metadata.so
+solana program dump -u m GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCMZw spl_governance.so
@@ -1,16 +1,355 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
+import {
+  ComputeBudgetProgram,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import {
+  TokenStandard,
+  createCreateInstruction,
+  createMintInstruction,
+  createTransferInstruction,
+} from "@metaplex-foundation/mpl-token-metadata";
+import {
+  GoverningTokenConfigAccountArgs,
+  GoverningTokenType,
+  MintMaxVoteWeightSource,
+  withCreateRealm,
+} from "@solana/spl-governance";
+import { expect } from "chai";
 import { Staking } from "../target/types/staking";
 
+import { GLOBAL_AUTHORITY_SEED, USER_POOL_SEED, VOTER_WEIGHT_SEED } from "../lib/constant";
+import {
+  castNftVoteTx,
+  createInitUserTx,
+  createLockPnftTx,
+  createUnlockPnftTx,
+  createVoterWeightRecordTx,
+  relinquishNftVoteTx,
+  updateVoterWeightRecordTx,
+} from "../lib/scripts";
+import { findTokenRecordPda, getMasterEdition, getMetadata } from "../lib/util";
+
+// Loaded at genesis from tests/fixtures/spl_governance.so, see Anchor.toml
+const GOVERNANCE_PROGRAM_ID = new PublicKey("GovER5Lthms3bLBqWub97yVrMmEogzX7xNjdXpPPCMZw");
+
+// Verified creator of the test pNFTs, accepted as collection by the localnet build
+const collectionAuthority = Keypair.fromSecretKey(
+  Uint8Array.from(require("./fixtures/collection-authority.json"))
+);
+
 describe("staking", () => {
   // Configure the client to use the local cluster.
-  anchor.setProvider(anchor.AnchorProvider.env());
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
 
   const program = anchor.workspace.Staking as Program<Staking>;
+  const payer = provider.wallet.publicKey;
+
+  let communityMint: PublicKey;
+  let realm: PublicKey;
+  let voterWeightRecord: PublicKey;
+
+  const send = async (tx: Transaction) => provider.sendAndConfirm(tx, [], { commitment: "confirmed" });
+  const sendRaw = async (tx: Buffer) => {
+    const signature = await provider.connection.sendRawTransaction(tx);
+    await provider.connection.confirmTransaction(signature, "confirmed");
+  };
+  const airdrop = async (address: PublicKey) => {
+    const signature = await provider.connection.requestAirdrop(address, 10 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(signature, "confirmed");
+  };
+  const getUserPoolPda = (owner: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from(USER_POOL_SEED), owner.toBytes()], program.programId)[0];
+
+  // Mint a pNFT without rule set to owner, created by the collection authority as verified creator
+  const mintPnft = async (owner: PublicKey) => {
+    const mint = Keypair.generate();
+    const metadata = await getMetadata(mint.publicKey);
+    const masterEdition = await getMasterEdition(mint.publicKey);
+    const token = getAssociatedTokenAddressSync(mint.publicKey, owner);
+
+    const create = createCreateInstruction(
+      {
+        metadata,
+        masterEdition,
+        mint: mint.publicKey,
+        authority: collectionAuthority.publicKey,
+        payer: collectionAuthority.publicKey,
+        updateAuthority: collectionAuthority.publicKey,
+        sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        splTokenProgram: TOKEN_PROGRAM_ID,
+      },
+      {
+        createArgs: {
+          __kind: "V1",
+          assetData: {
+            name: "Staking Test",
+            symbol: "STK",
+            uri: "",
+            sellerFeeBasisPoints: 0,
+            creators: [{ address: collectionAuthority.publicKey, verified: true, share: 100 }],
+            primarySaleHappened: false,
+            isMutable: true,
+            tokenStandard: TokenStandard.ProgrammableNonFungible,
+            collection: { key: collectionAuthority.publicKey, verified: false },
+            uses: null,
+            collectionDetails: null,
+            ruleSet: null,
+          },
+          decimals: 0,
+          printSupply: { __kind: "Zero" },
+        },
+      }
+    );
+    // The mint account is created by the instruction, so it signs
+    create.keys.find((key) => key.pubkey.equals(mint.publicKey)).isSigner = true;
+
+    const mintTo = createMintInstruction(
+      {
+        token,
+        tokenOwner: owner,
+        metadata,
+        masterEdition,
+        tokenRecord: findTokenRecordPda(mint.publicKey, token),
+        mint: mint.publicKey,
+        authority: collectionAuthority.publicKey,
+        payer: collectionAuthority.publicKey,
+        sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        splTokenProgram: TOKEN_PROGRAM_ID,
+        splAtaProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      },
+      { mintArgs: { __kind: "V1", amount: 1, authorizationData: null } }
+    );
+
+    await provider.sendAndConfirm(
+      new Transaction().add(ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }), create, mintTo),
+      [collectionAuthority, mint],
+      { commitment: "confirmed" }
+    );
+    return mint.publicKey;
+  };
+
+  // Transfer an unlocked pNFT, the destination token account is created by the instruction
+  const transferPnft = async (mint: PublicKey, from: Keypair, to: PublicKey) => {
+    const token = getAssociatedTokenAddressSync(mint, from.publicKey);
+    const destination = getAssociatedTokenAddressSync(mint, to);
+
+    const transfer = createTransferInstruction(
+      {
+        token,
+        tokenOwner: from.publicKey,
+        destination,
+        destinationOwner: to,
+        mint,
+        metadata: await getMetadata(mint),
+        edition: await getMasterEdition(mint),
+        ownerTokenRecord: findTokenRecordPda(mint, token),
+        destinationTokenRecord: findTokenRecordPda(mint, destination),
+        authority: from.publicKey,
+        payer: from.publicKey,
+        systemProgram: SystemProgram.programId,
+        sysvarInstructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+        splTokenProgram: TOKEN_PROGRAM_ID,
+        splAtaProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      },
+      { transferArgs: { __kind: "V1", amount: 1, authorizationData: null } }
+    );
+
+    await provider.sendAndConfirm(
+      new Transaction().add(ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }), transfer),
+      [from],
+      { commitment: "confirmed" }
+    );
+  };
+
+  // New wallet with a voter weight record in the realm
+  const createVoter = async () => {
+    const voter = Keypair.generate();
+    await airdrop(voter.publicKey);
+    await send(
+      await createVoterWeightRecordTx(payer, voter.publicKey, GOVERNANCE_PROGRAM_ID, realm, communityMint, program)
+    );
+    return voter;
+  };
+  const getVoterWeight = async (owner: PublicKey) => {
+    const [record] = PublicKey.findProgramAddressSync(
+      [Buffer.from(VOTER_WEIGHT_SEED), realm.toBytes(), communityMint.toBytes(), owner.toBytes()],
+      program.programId
+    );
+    return (await program.account.voterWeightRecord.fetch(record)).voterWeight.toNumber();
+  };
+
+  before(async () => {
+    await airdrop(collectionAuthority.publicKey);
+    communityMint = await createMint(provider.connection, (provider.wallet as any).payer, payer, null, 6);
+
+    const [globalPool] = PublicKey.findProgramAddressSync(
+      [Buffer.from(GLOBAL_AUTHORITY_SEED)],
+      program.programId
+    );
+    await program.methods
+      .initialize(new anchor.BN(1_000_000_000), true)
+      .accounts({
+        admin: payer,
+        globalPool,
+        rewardMint: communityMint,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+
+    // Realm using this program as its community voter weight addin
+    const instructions = [];
+    realm = await withCreateRealm(
+      instructions,
+      GOVERNANCE_PROGRAM_ID,
+      3,
+      `staking-${Keypair.generate().publicKey.toBase58().slice(0, 8)}`,
+      payer,
+      communityMint,
+      payer,
+      undefined,
+      MintMaxVoteWeightSource.FULL_SUPPLY_FRACTION,
+      new anchor.BN(1),
+      new GoverningTokenConfigAccountArgs({
+        voterWeightAddin: program.programId,
+        maxVoterWeightAddin: undefined,
+        tokenType: GoverningTokenType.Liquid,
+      })
+    );
+    await send(new Transaction().add(...instructions));
+
+    await send(
+      await createVoterWeightRecordTx(payer, payer, GOVERNANCE_PROGRAM_ID, realm, communityMint, program)
+    );
+    await send(await createInitUserTx(payer, program, null));
+
+    [voterWeightRecord] = PublicKey.findProgramAddressSync(
+      [Buffer.from(VOTER_WEIGHT_SEED), realm.toBytes(), communityMint.toBytes(), payer.toBytes()],
+      program.programId
+    );
+  });
+
+  it("creates the voter weight record of a realm", async () => {
+    const record = await program.account.voterWeightRecord.fetch(voterWeightRecord);
+    expect(record.realm.toBase58()).to.equal(realm.toBase58());
+    expect(record.governingTokenMint.toBase58()).to.equal(communityMint.toBase58());
+    expect(record.governingTokenOwner.toBase58()).to.equal(payer.toBase58());
+  });
+
+  it("binds updated weight to the action", async () => {
+    await send(
+      await updateVoterWeightRecordTx(payer, realm, communityMint, { createProposal: {} }, program)
+    );
+
+    const record = await program.account.voterWeightRecord.fetch(voterWeightRecord);
+    expect(record.voterWeight.toNumber()).to.equal(0);
+    expect(record.weightAction).to.deep.equal({ createProposal: {} });
+    expect(record.weightActionTarget).to.equal(null);
+  });
+
+  it("rejects vote weight outside cast_nft_vote", async () => {
+    try {
+      await send(
+        await updateVoterWeightRecordTx(payer, realm, communityMint, { castVote: {} } as any, program)
+      );
+      expect.fail("update_voter_weight_record accepted CastVote");
+    } catch (e) {
+      expect(`${e}`).to.contain("UseCastNftVote");
+    }
+  });
+
+  it("binds vote weight to the proposal", async () => {
+    const proposal = Keypair.generate().publicKey;
+
+    await send(await castNftVoteTx(payer, realm, communityMint, proposal, program));
+    let record = await program.account.voterWeightRecord.fetch(voterWeightRecord);
+    expect(record.weightAction).to.deep.equal({ castVote: {} });
+    expect(record.weightActionTarget.toBase58()).to.equal(proposal.toBase58());
+
+    // No staked NFT, so there is no vote record to close and no weight to add
+    await send(await relinquishNftVoteTx(payer, realm, communityMint, proposal, program));
+    await send(await castNftVoteTx(payer, realm, communityMint, proposal, program));
+    record = await program.account.voterWeightRecord.fetch(voterWeightRecord);
+    expect(record.voterWeight.toNumber()).to.equal(0);
+  });
+
+  it("locks into a pool created by init_user once it has grown", async () => {
+    const wallet = provider.wallet as anchor.Wallet;
+    const userPool = getUserPoolPda(payer);
+    const initialSize = (await provider.connection.getAccountInfo(userPool)).data.length;
+
+    // The first lock grows the pool for DEFAULT_STAKE_SIZE items and the fourth grows it again
+    for (let i = 0; i < 4; i++) {
+      await sendRaw(await createLockPnftTx(wallet, await mintPnft(payer), program, provider.connection));
+    }
+
+    const user = await program.account.userPool.fetch(userPool);
+    expect(user.itemCount.toNumber()).to.equal(4);
+    expect((await provider.connection.getAccountInfo(userPool)).data.length).to.be.greaterThan(initialSize);
+  });
+
+  it("creates the user pool on the first lock and locks again", async () => {
+    const owner = Keypair.generate();
+    await airdrop(owner.publicKey);
+    const wallet = new anchor.Wallet(owner);
+
+    await sendRaw(await createLockPnftTx(wallet, await mintPnft(owner.publicKey), program, provider.connection));
+    await sendRaw(await createLockPnftTx(wallet, await mintPnft(owner.publicKey), program, provider.connection));
+
+    const user = await program.account.userPool.fetch(getUserPoolPda(owner.publicKey));
+    expect(user.owner.toBase58()).to.equal(owner.publicKey.toBase58());
+    expect(user.itemCount.toNumber()).to.equal(2);
+  });
+
+  it("counts a staked NFT once per proposal, even re-staked by another wallet", async () => {
+    const proposal = Keypair.generate().publicKey;
+    const first = await createVoter();
+    const second = await createVoter();
+    const firstWallet = new anchor.Wallet(first);
+    const secondWallet = new anchor.Wallet(second);
+
+    const mint = await mintPnft(first.publicKey);
+    await sendRaw(await createLockPnftTx(firstWallet, mint, program, provider.connection));
+    await provider.sendAndConfirm(
+      await castNftVoteTx(first.publicKey, realm, communityMint, proposal, program),
+      [first],
+      { commitment: "confirmed" }
+    );
+    expect(await getVoterWeight(first.publicKey)).to.be.greaterThan(0);
+
+    // The vote record of the NFT outlives its stake
+    await sendRaw(await createUnlockPnftTx(firstWallet, mint, program, provider.connection));
+    await transferPnft(mint, first, second.publicKey);
+    await sendRaw(await createLockPnftTx(secondWallet, mint, program, provider.connection));
+
+    await provider.sendAndConfirm(
+      await castNftVoteTx(second.publicKey, realm, communityMint, proposal, program),
+      [second],
+      { commitment: "confirmed" }
+    );
+    expect(await getVoterWeight(second.publicKey)).to.equal(0);
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+    // Its weight is still counted for any other proposal
+    await provider.sendAndConfirm(
+      await castNftVoteTx(second.publicKey, realm, communityMint, Keypair.generate().publicKey, program),
+      [second],
+      { commitment: "confirmed" }
+    );
+    expect(await getVoterWeight(second.publicKey)).to.be.greaterThan(0);
   });
 });